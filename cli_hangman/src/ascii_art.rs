@@ -0,0 +1,98 @@
+use hangman_core::*;
+
+const WIDTH: usize = 11;
+const HEIGHT: usize = 8;
+
+/// draws the hangman as ascii art, one line per row
+pub fn hangman(game: &Game) -> String {
+    let mut canvas = [[' '; WIDTH]; HEIGHT];
+    let draw_count = usize::try_from(game.guess_count)
+        .unwrap_or(0)
+        .min(game.difficulty.0.len());
+    for elem in &game.difficulty.0[..draw_count] {
+        for &(row, col, c) in element(*elem) {
+            canvas[row][col] = c;
+        }
+    }
+    canvas
+        .iter()
+        .map(|row| row.iter().collect::<String>().trim_end().to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// the (row, column, character) cells that make up a drawing element
+fn element(elem: HangmanDrawingElements) -> &'static [(usize, usize, char)] {
+    use HangmanDrawingElements::*;
+    match elem {
+        Base => &[
+            (7, 2, '='),
+            (7, 3, '='),
+            (7, 4, '='),
+            (7, 5, '='),
+            (7, 6, '='),
+        ],
+        VerticalBeam => &[
+            (1, 4, '|'),
+            (2, 4, '|'),
+            (3, 4, '|'),
+            (4, 4, '|'),
+            (5, 4, '|'),
+            (6, 4, '|'),
+        ],
+        HorizontalBeam => &[
+            (0, 4, '+'),
+            (0, 5, '-'),
+            (0, 6, '-'),
+            (0, 7, '-'),
+            (0, 8, '+'),
+        ],
+        SupportBeam => &[(1, 5, '/')],
+        Rope => &[(1, 8, '|')],
+        Head => &[(2, 6, '('), (2, 10, ')')],
+        Torso => &[(3, 8, '|'), (4, 8, '|')],
+        LeftArm => &[(3, 7, '/')],
+        RightArm => &[(3, 9, '\\')],
+        LeftLeg => &[(5, 7, '/')],
+        RightLeg => &[(5, 9, '\\')],
+        LeftEye => &[(2, 7, 'o')],
+        RightEye => &[(2, 9, 'o')],
+        Mouth => &[(2, 8, '-')],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the character drawn at the row and column, a space if nothing is
+    fn cell(drawing: &str, row: usize, col: usize) -> char {
+        drawing
+            .lines()
+            .nth(row)
+            .and_then(|line| line.chars().nth(col))
+            .unwrap_or(' ')
+    }
+
+    #[test]
+    fn draws_one_more_element_per_miss_in_order() {
+        let mut game = Game::new("CAT".to_string(), DifficultyLevel::get_normal()).unwrap();
+        let misses: Vec<char> = "BDEFGHIJKLMNOPQRSUVWXYZ".chars().collect();
+        for count in 0..=game.difficulty.lives() {
+            if count > 0 {
+                game.guess(misses[count - 1]);
+            }
+            let drawing = hangman(&game);
+            for (i, elem) in game.difficulty.0.iter().enumerate() {
+                for &(row, col, c) in element(*elem) {
+                    let expected = if i < count { c } else { ' ' };
+                    assert_eq!(
+                        cell(&drawing, row, col),
+                        expected,
+                        "{elem:?} after {count} misses"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,546 @@
+mod ascii_art;
+use hangman_core::network::{self, Connection, Listener, Message, NetworkError};
+use hangman_core::replay::{self, Move, Replay};
+use hangman_core::*;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// number of blank lines printed to scroll the secret word out of view
+const SCROLL_LINES: usize = 50;
+
+fn main() {
+    let (difficulties, errors) = config::get_all_difficulties();
+    for e in errors {
+        eprintln!("skipping custom difficulties, {e}");
+    }
+    let mut difficulty = DifficultyLevel::get_easiest();
+    let mut max_hints = DEFAULT_MAX_HINTS;
+    let mut solve_penalty = None;
+    let mut language = Language::default();
+    let mut word_list_path = None;
+    let mut strict = false;
+    let mut show_stats = false;
+    let mut players = None;
+    let mut best_of = None;
+    let mut host_address = None;
+    let mut join_address = None;
+    let mut name = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--hints" || arg == "--solve-penalty" || arg == "--best-of" {
+            match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) if arg == "--hints" => max_hints = n,
+                Some(n) if arg == "--best-of" => best_of = Some(n),
+                Some(n) => solve_penalty = Some(n),
+                None => {
+                    eprintln!("{arg} expects a number");
+                    std::process::exit(2);
+                }
+            }
+            continue;
+        }
+        if arg == "--players" {
+            // two names separated by a comma start a match where the players take turns
+            let names = args.next().unwrap_or_default();
+            match names.split_once(',') {
+                Some((first, second)) if !second.contains(',') => {
+                    players = Some([first.to_string(), second.to_string()])
+                }
+                _ => {
+                    eprintln!("{arg} expects two names separated by a comma");
+                    std::process::exit(2);
+                }
+            }
+            continue;
+        }
+        if arg == "--host" || arg == "--join" || arg == "--name" {
+            match args.next() {
+                Some(value) if arg == "--host" => host_address = Some(value),
+                Some(value) if arg == "--join" => join_address = Some(value),
+                Some(value) => name = Some(value),
+                None => {
+                    eprintln!("{arg} expects a value");
+                    std::process::exit(2);
+                }
+            }
+            continue;
+        }
+        if arg == "--stats" {
+            show_stats = true;
+            continue;
+        }
+        if arg == "--strict" {
+            // accented letters have to be guessed on their own
+            strict = true;
+            continue;
+        }
+        if arg == "--word-list" {
+            match args.next() {
+                Some(path) => word_list_path = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("{arg} expects a path to a word list");
+                    std::process::exit(2);
+                }
+            }
+            continue;
+        }
+        if arg == "--language" {
+            let name = args.next().unwrap_or_default();
+            language = match Language::find(&name) {
+                Some(l) => l,
+                None => {
+                    let codes = Language::get_all()
+                        .iter()
+                        .map(|l| l.code())
+                        .collect::<Vec<&str>>()
+                        .join(", ");
+                    eprintln!("unknown language \"{name}\", expected one of: {codes}");
+                    std::process::exit(2);
+                }
+            };
+            continue;
+        }
+        difficulty = match difficulties
+            .iter()
+            .find(|d| d.name().eq_ignore_ascii_case(&arg))
+        {
+            Some(d) => d.clone(),
+            None => {
+                let names = difficulties
+                    .iter()
+                    .map(|d| d.name().to_lowercase())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                eprintln!("unknown difficulty \"{arg}\", expected one of: {names}");
+                std::process::exit(2);
+            }
+        };
+    }
+    let solve_penalty = match solve_penalty {
+        Some(penalty) if !difficulty.allows_solve_penalty(penalty) => {
+            eprintln!(
+                "--solve-penalty expects 1 to {} lives for {}",
+                difficulty.lives(),
+                difficulty.name()
+            );
+            std::process::exit(2);
+        }
+        Some(penalty) => penalty,
+        // the default never costs more lives than the difficulty level has
+        None => DEFAULT_SOLVE_PENALTY.min(u32::try_from(difficulty.lives()).unwrap_or(u32::MAX)),
+    };
+    if let Some(path) = word_list_path {
+        language = match WordList::load(&path, &language) {
+            Ok(word_list) => language.with_word_list(word_list),
+            Err(e) => {
+                eprintln!("can not use the word list, {e}");
+                std::process::exit(1);
+            }
+        };
+    }
+    let stats_path = stats::stats_file_path();
+    let mut player_stats = match stats_path.as_deref().map(stats::Stats::load) {
+        Some(Ok(loaded)) => loaded,
+        Some(Err(e)) => {
+            eprintln!("starting with empty statistics, {e}");
+            stats::Stats::default()
+        }
+        None => stats::Stats::default(),
+    };
+    if show_stats {
+        for difficulty in &difficulties {
+            println!("{}", stats_line(difficulty, &player_stats));
+        }
+        return;
+    }
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut game = Game {
+        difficulty,
+        max_hints,
+        solve_penalty,
+        language,
+        ..Game::default()
+    };
+    game.set_fold_diacritics(!strict);
+    // over the network the host sets the words and the guest guesses them
+    let network_result = match (host_address, join_address) {
+        (Some(address), _) => Some(
+            Listener::bind(&address, name.as_deref().unwrap_or("Host"))
+                .and_then(|listener| host(&listener, &game, &mut lines)),
+        ),
+        (None, Some(address)) => Some(
+            Connection::join(&address, name.as_deref().unwrap_or("Guest")).and_then(|connection| {
+                join(
+                    connection,
+                    &mut player_stats,
+                    stats_path.as_deref(),
+                    &mut lines,
+                )
+            }),
+        ),
+        (None, None) => None,
+    };
+    if let Some(result) = network_result {
+        if let Err(e) = result {
+            eprintln!("the network game ended, {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    let mut hot_seat = match (players, best_of) {
+        (None, None) => None,
+        (players, best_of) => Some(hotseat::Match::new(
+            players.unwrap_or_default(),
+            best_of.unwrap_or(hotseat::DEFAULT_BEST_OF),
+        )),
+    };
+    loop {
+        if let Some(m) = &hot_seat {
+            println!(
+                "Round {} of at most {}: {} sets the word, {} guesses.",
+                m.round_number(),
+                m.best_of,
+                m.players[m.setter()],
+                m.players[m.guesser()]
+            );
+        }
+        game = match select_word(&game, &mut lines, true) {
+            Some(g) => g,
+            None => return,
+        };
+        let mut replay = Replay::new(&game);
+        let won = match play(&mut game, &mut replay, &mut lines) {
+            Some(won) => won,
+            None => return,
+        };
+        if let Some(dir) = replay::replay_dir() {
+            if let Err(e) = replay.save_new(&dir) {
+                eprintln!("could not save the replay, {e}");
+            }
+        }
+        println!("{}", ascii_art::hangman(&game));
+        if won {
+            println!("The word was {}, the guesser wins!", game.word);
+        } else {
+            println!("The word was {}, the guesser lost.", game.word);
+        }
+        // the rounds of a match are guessed by two players in turn, so they are not in the statistics
+        if hot_seat.is_none() {
+            player_stats.record(&game);
+            if let Some(path) = &stats_path {
+                if let Err(e) = player_stats.save(path) {
+                    eprintln!("could not save the statistics, {e}");
+                }
+            }
+            println!("{}", stats_line(&game.difficulty, &player_stats));
+        }
+        if let Some(m) = &mut hot_seat {
+            m.record(&game);
+            println!("{}", m.scoreboard());
+            if !m.is_over() {
+                continue;
+            }
+            match m.winner() {
+                Some(winner) => println!("{} wins the match!", m.players[winner]),
+                None => println!("The match is a draw."),
+            }
+            return;
+        }
+        match prompt(&mut lines, "Play again? [y/N] ") {
+            Some(answer) if answer.trim().eq_ignore_ascii_case("y") => continue,
+            _ => return,
+        }
+    }
+}
+
+/// asks the setter for a word until a valid one is entered, returns None on end of input,
+/// scroll hides the word from a guesser at the same screen
+fn select_word(
+    game: &Game,
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+    scroll: bool,
+) -> Option<Game> {
+    loop {
+        let word = prompt(
+            lines,
+            &format!(
+                "Enter a word or phrase to be guessed ({}): ",
+                game.difficulty.name()
+            ),
+        )?;
+        match Game::from_game(game, word.trim().to_string()) {
+            Ok(g) => {
+                if scroll {
+                    print!("{}", "\n".repeat(SCROLL_LINES));
+                }
+                return Some(g);
+            }
+            Err(e) => println!("That word can not be used, {e}."),
+        }
+    }
+}
+
+/// lets the guesser guess until the game ends and records the moves, returns None on end of input
+fn play(
+    game: &mut Game,
+    replay: &mut Replay,
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+) -> Option<bool> {
+    let start = Instant::now();
+    loop {
+        if let Some(end_state) = game.get_game_state() {
+            return Some(end_state);
+        }
+        print_board(game);
+        let input = prompt(lines, "Guess a letter, or the whole word: ")?;
+        if input.trim() == "?" {
+            let hint = game
+                .most_common_hidden_letter()
+                .ok_or(HintError::GameOver)
+                .and_then(|c| game.hint(c).map(|_| c));
+            match hint {
+                Ok(c) => {
+                    replay.record(start.elapsed().as_secs_f64(), Move::Hint(c));
+                    println!("Hint: the word has {c} in it.")
+                }
+                Err(e) => println!("No hint, {e}."),
+            }
+            continue;
+        }
+        let mut chars = input.trim().chars();
+        let c = match (chars.next(), chars.next()) {
+            (Some(c), None) => game.language.to_uppercase_char(c),
+            (Some(_), Some(_)) => {
+                let outcome = game.solve(&input);
+                if matches!(outcome, SolveOutcome::Correct | SolveOutcome::Wrong) {
+                    let attempt = input.trim().to_string();
+                    replay.record(start.elapsed().as_secs_f64(), Move::Solve(attempt));
+                }
+                match outcome {
+                    SolveOutcome::Correct => println!("That is the word!"),
+                    SolveOutcome::Wrong => println!(
+                        "{} is not the word, that costs {} lives.",
+                        game.language.to_uppercase(input.trim()),
+                        game.solve_penalty
+                    ),
+                    SolveOutcome::Empty | SolveOutcome::GameOver => {}
+                }
+                continue;
+            }
+            _ => {
+                println!("Type a letter or a word.");
+                continue;
+            }
+        };
+        let outcome = game.guess(c);
+        if matches!(outcome, GuessOutcome::Hit(_) | GuessOutcome::Miss) {
+            replay.record(start.elapsed().as_secs_f64(), Move::Guess(c));
+        }
+        match outcome {
+            GuessOutcome::Hit(positions) if positions.len() == 1 => println!("{c} is in the word!"),
+            GuessOutcome::Hit(positions) => {
+                println!("{c} is in the word {} times!", positions.len())
+            }
+            GuessOutcome::Miss => println!("{c} is not in the word."),
+            GuessOutcome::AlreadyGuessed => println!("{c} has already been guessed."),
+            GuessOutcome::NotInAlphabet => println!("{c} is not a letter in the alphabet."),
+        }
+    }
+}
+
+/// hosts games over the network until the host stops setting words or the guest leaves
+fn host(
+    listener: &Listener,
+    settings: &Game,
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+) -> Result<(), NetworkError> {
+    println!(
+        "Waiting for a player to join on {}...",
+        listener.local_addr()?
+    );
+    let mut connection = loop {
+        match listener.accept() {
+            Ok(connection) => break connection,
+            Err(e @ NetworkError::Io(_)) => return Err(e),
+            // someone who is not a hangman game of this version is turned away
+            Err(e) => eprintln!("a player could not join, {e}"),
+        }
+    };
+    let guest = connection.peer_name().to_string();
+    println!("{guest} joined, they guess the words you set.");
+    loop {
+        connection.send(&Message::Setting)?;
+        let mut game = match select_word(settings, lines, false) {
+            Some(g) => g,
+            None => return connection.send(&Message::Bye),
+        };
+        let mut replay = Replay::new(&game);
+        let start = Instant::now();
+        connection.send(&network::game_message(&game))?;
+        while game.get_game_state().is_none() {
+            // the host keeps the game, the guest only sends its moves
+            let action = match connection.receive()? {
+                Message::Guess { letter } => {
+                    let letter = game.language.to_uppercase_char(letter);
+                    let outcome = game.guess(letter);
+                    matches!(outcome, GuessOutcome::Hit(_) | GuessOutcome::Miss)
+                        .then_some(Move::Guess(letter))
+                }
+                Message::Hint => game
+                    .most_common_hidden_letter()
+                    .filter(|c| game.hint(*c).is_ok())
+                    .map(Move::Hint),
+                Message::Solve { attempt } => {
+                    let outcome = game.solve(&attempt);
+                    matches!(outcome, SolveOutcome::Correct | SolveOutcome::Wrong)
+                        .then_some(Move::Solve(attempt))
+                }
+                Message::Bye => {
+                    println!("{guest} left.");
+                    return Ok(());
+                }
+                _ => None,
+            };
+            if let Some(action) = action {
+                replay.record(start.elapsed().as_secs_f64(), action);
+                println!("{}", spaced(&game.in_progress_word()));
+            }
+            // every move is answered so the guest knows it can make the next one
+            connection.send(&network::game_message(&game))?;
+        }
+        if let Some(dir) = replay::replay_dir() {
+            if let Err(e) = replay.save_new(&dir) {
+                eprintln!("could not save the replay, {e}");
+            }
+        }
+        println!("{}", ascii_art::hangman(&game));
+        match game.get_game_state() {
+            Some(true) => println!("The word was {}, {guest} wins!", game.word),
+            _ => println!("The word was {}, {guest} lost.", game.word),
+        }
+        match prompt(lines, "Set another word? [y/N] ") {
+            Some(answer) if answer.trim().eq_ignore_ascii_case("y") => continue,
+            _ => return connection.send(&Message::Bye),
+        }
+    }
+}
+
+/// guesses the words set by the host until either player leaves
+fn join(
+    mut connection: Connection,
+    player_stats: &mut stats::Stats,
+    stats_path: Option<&Path>,
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+) -> Result<(), NetworkError> {
+    let host = connection.peer_name().to_string();
+    println!("Joined {host}, they set the words and you guess them.");
+    loop {
+        let game = match connection.receive()? {
+            Message::State { view } => view.to_game(),
+            Message::GameOver { game } => *game,
+            Message::Setting => {
+                println!("{host} is picking a word...");
+                continue;
+            }
+            Message::Bye => {
+                println!("{host} left.");
+                return Ok(());
+            }
+            _ => continue,
+        };
+        if let Some(won) = game.get_game_state() {
+            println!("{}", ascii_art::hangman(&game));
+            if won {
+                println!("The word was {}, you win!", game.word);
+            } else {
+                println!("The word was {}, you lost.", game.word);
+            }
+            player_stats.record(&game);
+            if let Some(path) = stats_path {
+                if let Err(e) = player_stats.save(path) {
+                    eprintln!("could not save the statistics, {e}");
+                }
+            }
+            println!("{}", stats_line(&game.difficulty, player_stats));
+            continue;
+        }
+        print_board(&game);
+        let message = loop {
+            let input = match prompt(lines, "Guess a letter, or the whole word: ") {
+                Some(input) => input,
+                None => return connection.send(&Message::Bye),
+            };
+            let mut chars = input.trim().chars();
+            match (chars.next(), chars.next()) {
+                (Some('?'), None) => break Message::Hint,
+                (Some(letter), None) => break Message::Guess { letter },
+                (Some(_), Some(_)) => {
+                    break Message::Solve {
+                        attempt: input.trim().to_string(),
+                    }
+                }
+                _ => println!("Type a letter or a word."),
+            }
+        };
+        connection.send(&message)?;
+    }
+}
+
+/// prints the hangman, the word so far and what is left of the lives and hints
+fn print_board(game: &Game) {
+    println!("{}", ascii_art::hangman(game));
+    println!();
+    println!("{}", spaced(&game.in_progress_word()));
+    println!("Guessed: {}", guessed_characters(game));
+    println!(
+        "Lives left: {}",
+        game.difficulty.lives() as i32 - game.guess_count
+    );
+    println!(
+        "Hints left: {} (type ? for a hint, costs a life)",
+        game.max_hints.saturating_sub(game.hints_used)
+    );
+}
+
+/// a summary of the statistics of the difficulty level
+fn stats_line(difficulty: &DifficultyLevel, player_stats: &stats::Stats) -> String {
+    match player_stats.get(difficulty) {
+        Some(s) => format!(
+            "{}: {} won, {} lost, streak {} (best {}), {:.1} misses on average",
+            difficulty.name(),
+            s.wins,
+            s.losses,
+            s.current_streak,
+            s.best_streak,
+            s.average_misses()
+        ),
+        None => format!("{}: no games played", difficulty.name()),
+    }
+}
+
+/// prints the prompt and reads one line, returns None on end of input
+fn prompt(lines: &mut impl Iterator<Item = io::Result<String>>, message: &str) -> Option<String> {
+    print!("{message}");
+    io::stdout().flush().ok()?;
+    lines.next()?.ok()
+}
+
+/// puts a space between each character so the blanks can be counted
+fn spaced(word: &str) -> String {
+    word.chars()
+        .map(|c| c.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// the characters that have been guessed so far
+fn guessed_characters(game: &Game) -> String {
+    game.guessable_characters
+        .iter()
+        .filter(|gc| gc.1)
+        .map(|gc| gc.0.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
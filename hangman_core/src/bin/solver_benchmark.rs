@@ -0,0 +1,235 @@
+//! plays the AI guesser against every dictionary word at every built in difficulty level
+//!
+//! usage: solver_benchmark [--format csv|json] [--hardest <count>] [--limit <count>]
+//!
+//! `--limit` plays an evenly spread sample of that many words instead of the whole dictionary
+use hangman_core::solver::Solver;
+use hangman_core::*;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::process::exit;
+use std::thread;
+
+/// the result of the guesser playing one word
+#[derive(Serialize)]
+struct WordResult {
+    word: &'static str,
+    misses: i32,
+    won: bool,
+    /// false if the guesser ran out of letters to guess before the game was over,
+    /// such games count as lost
+    finished: bool,
+}
+
+/// the results of the guesser at one difficulty level
+#[derive(Serialize)]
+struct DifficultyReport {
+    name: String,
+    lives: usize,
+    games: usize,
+    wins: usize,
+    /// games the guesser could not finish, included in games but not in wins
+    unfinished: usize,
+    /// dictionary words that can not be played at the difficulty level, not included in games
+    skipped: usize,
+    win_rate: f64,
+    /// number of games that ended with each number of misses, indexed by misses
+    miss_distribution: Vec<usize>,
+    /// the words that took the most misses, lost games first
+    hardest_words: Vec<WordResult>,
+}
+
+/// plays a game of the word with the guesser until it is over or the guesser gives up,
+/// None if the word can not be played at the difficulty level
+fn play(word: &'static str, difficulty: &DifficultyLevel) -> Option<WordResult> {
+    let mut game = Game::new(word.to_string(), difficulty.clone()).ok()?;
+    let mut solver = Solver::new(&game);
+    let (won, finished) = loop {
+        if let Some(won) = game.get_game_state() {
+            break (won, true);
+        }
+        match solver.next_guess(&game) {
+            Some(c) => game.guess(c),
+            None => break (false, false),
+        };
+    };
+    Some(WordResult {
+        word,
+        misses: game.guess_count,
+        won,
+        finished,
+    })
+}
+
+/// the words to play, every word or an evenly spread sample of at most limit words
+fn sample<T>(words: &[T], limit: Option<usize>) -> impl Iterator<Item = &T> {
+    let step = match limit {
+        Some(limit) => words.len().div_ceil(limit.max(1)).max(1),
+        None => 1,
+    };
+    words.iter().step_by(step)
+}
+
+fn benchmark(
+    difficulty: &DifficultyLevel,
+    words: &[&'static str],
+    hardest: usize,
+) -> DifficultyReport {
+    let mut results = Vec::new();
+    let mut skipped = 0;
+    // progress goes to stderr so it does not mix with the report
+    let progress_step = (words.len() / 20).max(1);
+    for (i, word) in words.iter().enumerate() {
+        match play(word, difficulty) {
+            Some(result) => results.push(result),
+            None => skipped += 1,
+        }
+        if (i + 1) % progress_step == 0 || i + 1 == words.len() {
+            eprintln!("{}: {}/{} words", difficulty.name(), i + 1, words.len());
+        }
+    }
+    let mut miss_distribution = vec![0; difficulty.lives() + 1];
+    for result in &results {
+        let misses = usize::try_from(result.misses)
+            .unwrap_or(0)
+            .min(difficulty.lives());
+        miss_distribution[misses] += 1;
+    }
+    let games = results.len();
+    let wins = results.iter().filter(|r| r.won).count();
+    let unfinished = results.iter().filter(|r| !r.finished).count();
+    results.sort_by(|a, b| a.won.cmp(&b.won).then(b.misses.cmp(&a.misses)));
+    results.truncate(hardest);
+    DifficultyReport {
+        name: difficulty.name().to_string(),
+        lives: difficulty.lives(),
+        games,
+        wins,
+        unfinished,
+        skipped,
+        win_rate: wins as f64 / games.max(1) as f64,
+        miss_distribution,
+        hardest_words: results,
+    }
+}
+
+/// writes the reports as rows of difficulty,kind,key,value
+fn write_csv(out: &mut impl Write, reports: &[DifficultyReport]) -> io::Result<()> {
+    writeln!(out, "difficulty,kind,key,value")?;
+    for report in reports {
+        writeln!(out, "{},lives,,{}", report.name, report.lives)?;
+        writeln!(out, "{},games,,{}", report.name, report.games)?;
+        writeln!(out, "{},wins,,{}", report.name, report.wins)?;
+        writeln!(out, "{},unfinished,,{}", report.name, report.unfinished)?;
+        writeln!(out, "{},skipped,,{}", report.name, report.skipped)?;
+        writeln!(out, "{},win_rate,,{:.4}", report.name, report.win_rate)?;
+        for (misses, count) in report.miss_distribution.iter().enumerate() {
+            writeln!(out, "{},misses,{misses},{count}", report.name)?;
+        }
+        for result in &report.hardest_words {
+            let kind = match (result.won, result.finished) {
+                (true, _) => "hardest_won",
+                (false, true) => "hardest_lost",
+                (false, false) => "hardest_unfinished",
+            };
+            writeln!(
+                out,
+                "{},{kind},{},{}",
+                report.name, result.word, result.misses
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn usage() -> ! {
+    eprintln!("usage: solver_benchmark [--format csv|json] [--hardest <count>] [--limit <count>]");
+    exit(2);
+}
+
+fn main() {
+    let mut json = false;
+    let mut hardest = 20;
+    let mut limit = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next().as_deref()) {
+            ("--format", Some("csv")) => json = false,
+            ("--format", Some("json")) => json = true,
+            ("--hardest", Some(count)) => hardest = count.parse().unwrap_or_else(|_| usage()),
+            ("--limit", Some(count)) => limit = Some(count.parse().unwrap_or_else(|_| usage())),
+            _ => usage(),
+        }
+    }
+    // each difficulty level is played on its own thread
+    let difficulties = DifficultyLevel::get_all();
+    let words: Vec<&str> = sample(&ENGLISH_WORD_LIST, limit)
+        .map(String::as_str)
+        .collect();
+    let words = &words;
+    let reports: Vec<DifficultyReport> = thread::scope(|scope| {
+        difficulties
+            .iter()
+            .map(|difficulty| scope.spawn(move || benchmark(difficulty, words, hardest)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().expect("a benchmark thread panicked"))
+            .collect()
+    });
+    if json {
+        match serde_json::to_string_pretty(&reports) {
+            Ok(out) => println!("{out}"),
+            Err(e) => {
+                eprintln!("could not write the report: {e}");
+                exit(1);
+            }
+        }
+    } else if let Err(e) = write_csv(&mut io::stdout().lock(), &reports) {
+        eprintln!("could not write the report: {e}");
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_spreads_over_the_words() {
+        let words: Vec<usize> = (0..10).collect();
+        assert_eq!(sample(&words, None).count(), 10);
+        assert_eq!(
+            sample(&words, Some(3)).copied().collect::<Vec<_>>(),
+            [0, 4, 8]
+        );
+        assert_eq!(sample(&words, Some(0)).count(), 1);
+    }
+
+    #[test]
+    fn report_counts_every_game() {
+        let difficulty = DifficultyLevel::get_easiest();
+        // QXZQ is not a dictionary word so it can not be played
+        let report = benchmark(&difficulty, &["CAT", "ZEBRA", "JAZZ", "QXZQ"], 2);
+        assert_eq!(report.games, 3);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.wins, 3);
+        assert_eq!(report.unfinished, 0);
+        assert_eq!(report.miss_distribution.len(), difficulty.lives() + 1);
+        assert_eq!(report.miss_distribution.iter().sum::<usize>(), 3);
+        assert_eq!(report.hardest_words.len(), 2);
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &[report]).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], "difficulty,kind,key,value");
+        assert!(rows.contains(&"Easiest,games,,3"));
+        assert!(rows.contains(&"Easiest,wins,,3"));
+        assert!(rows.contains(&"Easiest,unfinished,,0"));
+        assert!(rows.contains(&"Easiest,skipped,,1"));
+        let misses = rows
+            .iter()
+            .filter(|r| r.starts_with("Easiest,misses,"))
+            .count();
+        assert_eq!(misses, difficulty.lives() + 1);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::game::*;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// name of the file custom difficulty levels are read from
+pub const DIFFICULTY_FILE_NAME: &str = "difficulties.toml";
+
+/// the layout of a difficulty file, e.g.
+/// ```toml
+/// [[difficulty]]
+/// name = "Quick"
+/// elements = ["Rope", "Head", "Torso", "LeftArm", "RightArm", "LeftLeg", "RightLeg"]
+/// max_word_length = 6
+/// ```
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DifficultyFile {
+    #[serde(default)]
+    difficulty: Vec<DifficultyDefinition>,
+}
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DifficultyDefinition {
+    name: String,
+    elements: Vec<HangmanDrawingElements>,
+    max_word_length: Option<usize>,
+}
+
+/// the reasons a difficulty file can not be used
+#[derive(Debug)]
+pub enum ConfigError {
+    /// the file could not be read
+    Io(PathBuf, io::Error),
+    /// the file is not valid toml or does not have the expected layout
+    Parse(PathBuf, toml::de::Error),
+    /// a difficulty level in the file breaks a rule
+    Invalid {
+        path: PathBuf,
+        name: String,
+        reason: &'static str,
+    },
+}
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            ConfigError::Parse(path, e) => write!(f, "{}: {e}", path.display()),
+            ConfigError::Invalid { path, name, reason } => {
+                write!(f, "{}: difficulty \"{name}\" {reason}", path.display())
+            }
+        }
+    }
+}
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io(_, e) => Some(e),
+            ConfigError::Parse(_, e) => Some(e),
+            ConfigError::Invalid { .. } => None,
+        }
+    }
+}
+
+/// the places a difficulty file is looked for, next to the executable and in the user config dir
+pub fn difficulty_file_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        paths.push(dir.join(DIFFICULTY_FILE_NAME));
+    }
+    if let Some(dir) = dirs::config_dir() {
+        paths.push(dir.join("hangman").join(DIFFICULTY_FILE_NAME));
+    }
+    paths
+}
+
+/// reads and validates the difficulty levels in a file,
+/// existing holds the levels that are already known so names are not reused
+pub fn load_difficulties(
+    path: &Path,
+    existing: &[DifficultyLevel],
+) -> Result<Vec<DifficultyLevel>, ConfigError> {
+    let source = fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+    let file: DifficultyFile =
+        toml::from_str(&source).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))?;
+    let mut levels: Vec<DifficultyLevel> = Vec::new();
+    for definition in file.difficulty {
+        let invalid = |reason| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            name: definition.name.clone(),
+            reason,
+        };
+        if definition.name.trim().is_empty() {
+            return Err(invalid("has an empty name"));
+        }
+        if existing
+            .iter()
+            .chain(levels.iter())
+            .any(|d| d.name().eq_ignore_ascii_case(&definition.name))
+        {
+            return Err(invalid("uses a name that is already taken"));
+        }
+        if definition.elements.is_empty() {
+            return Err(invalid("has no drawing elements"));
+        }
+        for (i, elem) in definition.elements.iter().enumerate() {
+            if definition.elements[..i].contains(elem) {
+                return Err(invalid("lists a drawing element more than once"));
+            }
+        }
+        if definition.max_word_length == Some(0) {
+            return Err(invalid("has a max word length of 0"));
+        }
+        levels.push(DifficultyLevel::new(
+            definition.name,
+            definition.elements,
+            definition.max_word_length,
+        ));
+    }
+    Ok(levels)
+}
+
+/// the built in difficulty levels followed by the custom ones from every difficulty file found,
+/// files that can not be used are skipped and reported in the list of errors
+pub fn get_all_difficulties() -> (Vec<DifficultyLevel>, Vec<ConfigError>) {
+    let mut levels = DifficultyLevel::get_all();
+    let mut errors = Vec::new();
+    for path in difficulty_file_paths() {
+        if !path.is_file() {
+            continue;
+        }
+        match load_difficulties(&path, &levels) {
+            Ok(custom) => levels.extend(custom),
+            Err(e) => errors.push(e),
+        }
+    }
+    (levels, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    fn load(name: &str, toml: &str) -> Result<Vec<DifficultyLevel>, ConfigError> {
+        let path = temp_path(&format!("{name}.toml"));
+        fs::write(&path, toml).unwrap();
+        let result = load_difficulties(&path, &DifficultyLevel::get_all());
+        fs::remove_file(&path).unwrap();
+        result
+    }
+
+    fn reason(result: Result<Vec<DifficultyLevel>, ConfigError>) -> &'static str {
+        match result {
+            Err(ConfigError::Invalid { reason, .. }) => reason,
+            _ => panic!("expected the level to be invalid"),
+        }
+    }
+
+    #[test]
+    fn loads_custom_levels() {
+        let levels = load(
+            "valid",
+            r#"
+            [[difficulty]]
+            name = "Quick"
+            elements = ["Rope", "Head", "Torso"]
+            max_word_length = 6
+            "#,
+        )
+        .unwrap();
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels[0].name(), "Quick");
+        assert_eq!(levels[0].lives(), 3);
+        assert_eq!(levels[0].max_word_length(), Some(6));
+    }
+
+    #[test]
+    fn rejects_invalid_levels() {
+        let level = |name: &str, elements: &str, max: &str| {
+            format!("[[difficulty]]\nname = \"{name}\"\nelements = [{elements}]\n{max}")
+        };
+        assert_eq!(
+            reason(load("empty-name", &level(" ", "\"Head\"", ""))),
+            "has an empty name"
+        );
+        assert_eq!(
+            reason(load("taken", &level("normal", "\"Head\"", ""))),
+            "uses a name that is already taken"
+        );
+        assert_eq!(
+            reason(load("no-elements", &level("A", "", ""))),
+            "has no drawing elements"
+        );
+        assert_eq!(
+            reason(load("twice", &level("A", "\"Head\", \"Head\"", ""))),
+            "lists a drawing element more than once"
+        );
+        assert_eq!(
+            reason(load("zero", &level("A", "\"Head\"", "max_word_length = 0"))),
+            "has a max word length of 0"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let result = load(
+            "unknown",
+            "[[difficulty]]\nname = \"A\"\nelements = [\"Head\"]\nlives = 3\n",
+        );
+        assert!(matches!(result, Err(ConfigError::Parse(..))));
+    }
+}
@@ -0,0 +1,682 @@
+use crate::language::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HangmanDrawingElements {
+    Base = 1,
+    VerticalBeam = 2,
+    HorizontalBeam = 3,
+    Rope = 4,
+    Head = 5,
+    Torso = 6,
+    LeftArm = 7,
+    RightArm = 8,
+    LeftLeg = 9,
+    RightLeg = 10,
+    // extras for easier than default below
+    SupportBeam = 11,
+    LeftEye = 12,
+    RightEye = 13,
+    Mouth = 14,
+}
+use HangmanDrawingElements::*;
+/// the drawing element order and the different difficulty versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DifficultyLevel(pub Vec<HangmanDrawingElements>, String, Option<usize>);
+impl DifficultyLevel {
+    /// create a difficulty level, the game is lost when every element has been drawn
+    pub fn new(
+        name: String,
+        elements: Vec<HangmanDrawingElements>,
+        max_word_length: Option<usize>,
+    ) -> DifficultyLevel {
+        DifficultyLevel(elements, name, max_word_length)
+    }
+    /// the display name of the difficulty level
+    pub fn name(&self) -> &str {
+        &self.1
+    }
+    /// the longest word that can be chosen, None if there is no limit
+    pub fn max_word_length(&self) -> Option<usize> {
+        self.2
+    }
+    /// the number of misses allowed before the game is lost
+    pub fn lives(&self) -> usize {
+        self.0.len()
+    }
+    /// whether a wrong solve attempt may cost this many lives, at least one and at most all of them
+    pub fn allows_solve_penalty(&self, penalty: u32) -> bool {
+        usize::try_from(penalty).is_ok_and(|p| (1..=self.lives()).contains(&p))
+    }
+    /// all built in difficulty levels, from easiest to hardest
+    pub fn get_all() -> Vec<DifficultyLevel> {
+        vec![
+            DifficultyLevel::get_easiest(),
+            DifficultyLevel::get_normal(),
+            DifficultyLevel::get_hard(),
+            DifficultyLevel::get_hardest(),
+        ]
+    }
+    pub fn get_easiest() -> DifficultyLevel {
+        DifficultyLevel(
+            vec![
+                Base,
+                VerticalBeam,
+                HorizontalBeam,
+                SupportBeam,
+                Rope,
+                Head,
+                Torso,
+                LeftArm,
+                RightArm,
+                LeftLeg,
+                RightLeg,
+                LeftEye,
+                RightEye,
+                Mouth,
+            ],
+            "Easiest".to_string(),
+            None,
+        )
+    }
+    pub fn get_normal() -> DifficultyLevel {
+        DifficultyLevel(
+            vec![
+                Base,
+                VerticalBeam,
+                HorizontalBeam,
+                SupportBeam,
+                Rope,
+                Head,
+                Torso,
+                LeftArm,
+                RightArm,
+                LeftLeg,
+                RightLeg,
+            ],
+            "Normal".to_string(),
+            None,
+        )
+    }
+    pub fn get_hard() -> DifficultyLevel {
+        DifficultyLevel(
+            vec![
+                Base,
+                VerticalBeam,
+                HorizontalBeam,
+                Rope,
+                Head,
+                Torso,
+                LeftArm,
+                RightArm,
+                LeftLeg,
+                RightLeg,
+            ],
+            "Hard".to_string(),
+            None,
+        )
+    }
+    pub fn get_hardest() -> DifficultyLevel {
+        DifficultyLevel(
+            vec![
+                VerticalBeam,
+                HorizontalBeam,
+                Rope,
+                Head,
+                Torso,
+                LeftArm,
+                RightArm,
+                LeftLeg,
+                RightLeg,
+            ],
+            "Hardest".to_string(),
+            None,
+        )
+    }
+}
+
+/// (the character, has it been guessed?)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GuessableChar(pub char, pub bool);
+impl GuessableChar {
+    pub fn new_set(character_set: &str) -> Vec<GuessableChar> {
+        character_set
+            .chars()
+            .map(|c| GuessableChar(c, false))
+            .collect()
+    }
+}
+/// the reasons a word can not be used to start a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameError {
+    /// no word was given
+    EmptyWord,
+    /// the word contains a character that is not in the alphabet
+    InvalidCharacter(char),
+    /// the word is longer than the difficulty level allows
+    WordTooLong(usize),
+    /// the word is not in the dictionary
+    NotInDictionary,
+    /// the phrase is only spaces, punctuation and digits
+    NoLetters,
+}
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::EmptyWord => write!(f, "no word has been typed"),
+            GameError::InvalidCharacter(c) => write!(f, "{c} is not in the alphabet"),
+            GameError::WordTooLong(max) => write!(f, "the word is longer than {max} letters"),
+            GameError::NotInDictionary => write!(f, "the word is not in the dictionary"),
+            GameError::NoLetters => write!(f, "there are no letters to guess"),
+        }
+    }
+}
+impl Error for GameError {}
+
+/// the result of guessing a character
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessOutcome {
+    /// the character is in the word, with the positions it was revealed at
+    Hit(Vec<usize>),
+    /// the character is not in the word
+    Miss,
+    /// the character has already been guessed, nothing changed
+    AlreadyGuessed,
+    /// the character can not be guessed, nothing changed
+    NotInAlphabet,
+}
+
+/// the reasons a hint can not be given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintError {
+    /// every hint of the game has been used
+    NoHintsLeft,
+    /// the hint would draw the last element and lose the game
+    NotEnoughLives,
+    /// the letter is not hidden in the word
+    NotHidden(char),
+    /// the game is already over
+    GameOver,
+}
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HintError::NoHintsLeft => write!(f, "there are no hints left"),
+            HintError::NotEnoughLives => write!(f, "a hint would lose the game"),
+            HintError::NotHidden(c) => write!(f, "{c} is not a hidden letter"),
+            HintError::GameOver => write!(f, "the game is over"),
+        }
+    }
+}
+impl Error for HintError {}
+
+/// the result of guessing the whole word at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveOutcome {
+    /// the attempt was the word, the game is won
+    Correct,
+    /// the attempt was not the word, the solve penalty was added to the guess count
+    Wrong,
+    /// no word was given, nothing changed
+    Empty,
+    /// the game is already over, nothing changed
+    GameOver,
+}
+
+/// characters other than digits that are shown from the start of a phrase and never guessed
+pub const REVEALED_CHARACTERS: &str = " '’-,.!?:;&";
+
+/// whether the character is shown from the start instead of being guessed
+pub fn is_revealed_character(c: char) -> bool {
+    c.is_ascii_digit() || REVEALED_CHARACTERS.contains(c)
+}
+
+/// trims a word or phrase and puts a single space between its words
+pub fn normalize_phrase(phrase: &str) -> String {
+    phrase.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// number of hints allowed per game unless changed
+pub const DEFAULT_MAX_HINTS: u32 = 3;
+/// number of drawing elements a wrong solve attempt costs unless changed
+pub const DEFAULT_SOLVE_PENALTY: u32 = 2;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Game {
+    /// the word to be guessed
+    pub word: String,
+    /// whether each character of the word is shown, spaces and punctuation are from the start
+    pub revealed: Vec<bool>,
+    // list of valid characters and whether they have already been guessed
+    pub guessable_characters: Vec<GuessableChar>,
+    /// number of guesses made
+    pub guess_count: i32,
+    /// the difficulty level, number and order of drawing elements on the hangman
+    pub difficulty: DifficultyLevel,
+    /// number of hints used, each one also counts as a guess
+    pub hints_used: u32,
+    /// number of hints allowed in the game
+    pub max_hints: u32,
+    /// number of drawing elements a wrong solve attempt costs
+    pub solve_penalty: u32,
+    /// the alphabet, dictionary and case rules of the word
+    pub language: Language,
+    /// whether guessing a base letter also reveals its accented forms in the alphabet, off in strict mode
+    pub fold_diacritics: bool,
+}
+impl Game {
+    /// create a new game from input word or phrase
+    pub fn new(word: String, difficulty: DifficultyLevel) -> Result<Game, GameError> {
+        Game::with_language(word, difficulty, Language::english())
+    }
+    /// create a new game from input word or phrase in a language,
+    /// spaces, punctuation and digits of a phrase are revealed from the start
+    pub fn with_language(
+        word: String,
+        difficulty: DifficultyLevel,
+        language: Language,
+    ) -> Result<Game, GameError> {
+        let word = language.to_uppercase(&normalize_phrase(&word));
+        if word.is_empty() {
+            return Err(GameError::EmptyWord);
+        }
+        for c in word.chars() {
+            if !language.is_letter(c) && !is_revealed_character(c) {
+                return Err(GameError::InvalidCharacter(c));
+            }
+        }
+        // the letters of each word in a phrase, spaces and punctuation split the words
+        let words: Vec<&str> = word
+            .split(|c: char| !language.is_letter(c))
+            .filter(|w| !w.is_empty())
+            .collect();
+        if words.is_empty() {
+            return Err(GameError::NoLetters);
+        }
+        if let Some(max) = difficulty.max_word_length() {
+            if words.iter().any(|w| w.chars().count() > max) {
+                return Err(GameError::WordTooLong(max));
+            }
+        }
+        // only single words can be checked, phrases are taken as they are
+        let is_phrase = word.chars().any(|c| !language.is_letter(c));
+        if !is_phrase && !language.is_word_in_dictionary(&word) {
+            return Err(GameError::NotInDictionary);
+        }
+        Ok(Game::with_valid_word(word, difficulty, language))
+    }
+    /// create a new game from an upper case word that is known to be valid,
+    /// e.g. one that was checked when it was recorded
+    pub(crate) fn with_valid_word(
+        word: String,
+        difficulty: DifficultyLevel,
+        language: Language,
+    ) -> Game {
+        Game {
+            guessable_characters: GuessableChar::new_set(&language.guessable_alphabet(true)),
+            guess_count: 0,
+            revealed: word.chars().map(|c| !language.is_letter(c)).collect(),
+            word,
+            difficulty,
+            hints_used: 0,
+            max_hints: DEFAULT_MAX_HINTS,
+            solve_penalty: DEFAULT_SOLVE_PENALTY,
+            language,
+            fold_diacritics: true,
+        }
+    }
+    /// create a new game with the settings of another game
+    pub fn from_game(game: &Game, word: String) -> Result<Game, GameError> {
+        let mut new_game =
+            Game::with_language(word, game.difficulty.clone(), game.language.clone())?;
+        new_game.max_hints = game.max_hints;
+        new_game.solve_penalty = game.solve_penalty;
+        new_game.set_fold_diacritics(game.fold_diacritics);
+        Ok(new_game)
+    }
+    /// turns folding of accented letters on or off and shows the matching letters for guessing,
+    /// call before the first guess
+    pub fn set_fold_diacritics(&mut self, fold_diacritics: bool) {
+        self.fold_diacritics = fold_diacritics;
+        self.guessable_characters =
+            GuessableChar::new_set(&self.language.guessable_alphabet(fold_diacritics));
+    }
+    /// the letter that has to be guessed to reveal the character
+    pub fn guess_letter(&self, c: char) -> char {
+        if self.fold_diacritics || self.language.is_extra_letter(c) {
+            self.language.fold_letter(c)
+        } else {
+            c
+        }
+    }
+    /// checks if the character is in the word and fill it in
+    pub fn guess(&mut self, char: char) -> GuessOutcome {
+        // an accented letter is guessed as its base letter when folding
+        let char = self.guess_letter(char);
+        // find the character in the list of guessable characters
+        let guessed = match self.guessable_characters.iter_mut().find(|gc| gc.0 == char) {
+            Some(gc) => gc,
+            None => return GuessOutcome::NotInAlphabet,
+        };
+        if guessed.1 {
+            return GuessOutcome::AlreadyGuessed;
+        }
+        guessed.1 = true;
+        // reveal all instances of the char in the word, including its accented forms
+        let mut positions = Vec::new();
+        let letters: Vec<char> = self.word.chars().map(|w| self.guess_letter(w)).collect();
+        for (i, (w, revealed)) in letters
+            .into_iter()
+            .zip(self.revealed.iter_mut())
+            .enumerate()
+        {
+            if w == char {
+                *revealed = true;
+                positions.push(i);
+            }
+        }
+        // increment the guess count if the character was not in the word
+        if positions.is_empty() {
+            self.guess_count += 1;
+            GuessOutcome::Miss
+        } else {
+            GuessOutcome::Hit(positions)
+        }
+    }
+    /// guesses the whole word at once, a correct attempt reveals the word and wins
+    pub fn solve(&mut self, attempt: &str) -> SolveOutcome {
+        if self.get_game_state().is_some() {
+            return SolveOutcome::GameOver;
+        }
+        let attempt = self.language.to_uppercase(&normalize_phrase(attempt));
+        if attempt.is_empty() {
+            return SolveOutcome::Empty;
+        }
+        let fold = |text: &str| -> String { text.chars().map(|c| self.guess_letter(c)).collect() };
+        if fold(&attempt) == fold(&self.word) {
+            self.revealed.iter_mut().for_each(|r| *r = true);
+            SolveOutcome::Correct
+        } else {
+            // the penalty never draws more than the whole hangman
+            let lives = i32::try_from(self.difficulty.lives()).unwrap_or(i32::MAX);
+            let penalty = i32::try_from(self.solve_penalty).unwrap_or(i32::MAX);
+            self.guess_count = self.guess_count.saturating_add(penalty).min(lives);
+            SolveOutcome::Wrong
+        }
+    }
+    /// each character of the word, None while it is hidden
+    pub fn progress(&self) -> impl Iterator<Item = Option<char>> + '_ {
+        self.word
+            .chars()
+            .zip(self.revealed.iter())
+            .map(|(c, &revealed)| revealed.then_some(c))
+    }
+    /// the word as it is shown to the guesser, with _ for each hidden letter
+    pub fn in_progress_word(&self) -> String {
+        self.progress().map(|c| c.unwrap_or('_')).collect()
+    }
+    /// the letters to guess for the parts of the word that have not been revealed,
+    /// once each in the order they appear
+    fn hidden_letters(&self) -> Vec<char> {
+        let mut letters: Vec<char> = Vec::new();
+        for (w, revealed) in self.word.chars().zip(self.revealed.iter()) {
+            let w = self.guess_letter(w);
+            if !revealed && !letters.contains(&w) {
+                letters.push(w);
+            }
+        }
+        letters
+    }
+    /// the hidden letter that appears the most times in the word
+    pub fn most_common_hidden_letter(&self) -> Option<char> {
+        self.hidden_letters().into_iter().rev().max_by_key(|&c| {
+            self.word
+                .chars()
+                .filter(|&w| self.guess_letter(w) == c)
+                .count()
+        })
+    }
+    /// a random hidden letter of the word
+    pub fn random_hidden_letter<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<char> {
+        self.hidden_letters().choose(rng).copied()
+    }
+    /// reveals a hidden letter at the cost of one drawing element
+    pub fn hint(&mut self, letter: char) -> Result<GuessOutcome, HintError> {
+        if self.get_game_state().is_some() {
+            return Err(HintError::GameOver);
+        }
+        if self.hints_used >= self.max_hints {
+            return Err(HintError::NoHintsLeft);
+        }
+        if self.guess_count + 1 >= self.difficulty.0.len() as i32 {
+            return Err(HintError::NotEnoughLives);
+        }
+        if !self.hidden_letters().contains(&letter) {
+            return Err(HintError::NotHidden(letter));
+        }
+        let outcome = self.guess(letter);
+        self.hints_used += 1;
+        self.guess_count += 1;
+        Ok(outcome)
+    }
+    /// returns Some if game is over, true on win and false on loss, None if game is not over
+    pub fn get_game_state(&self) -> Option<bool> {
+        if self.guess_count >= self.difficulty.0.len() as i32 {
+            return Some(false);
+        } else if self.revealed.iter().all(|&r| r) {
+            return Some(true);
+        }
+        None
+    }
+}
+impl Default for Game {
+    fn default() -> Self {
+        let language = Language::default();
+        Game {
+            guessable_characters: GuessableChar::new_set(&language.guessable_alphabet(true)),
+            guess_count: 0,
+            revealed: Vec::new(),
+            word: "".to_string(),
+            difficulty: DifficultyLevel::get_easiest(),
+            hints_used: 0,
+            max_hints: DEFAULT_MAX_HINTS,
+            solve_penalty: DEFAULT_SOLVE_PENALTY,
+            language,
+            fold_diacritics: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(word: &str) -> Game {
+        Game::new(word.to_string(), DifficultyLevel::get_normal()).unwrap()
+    }
+
+    #[test]
+    fn built_in_levels_are_distinct() {
+        let levels = DifficultyLevel::get_all();
+        for (i, a) in levels.iter().enumerate() {
+            for b in &levels[i + 1..] {
+                assert_ne!(a.lives(), b.lives(), "{} and {}", a.1, b.1);
+                assert_ne!(a.0, b.0, "{} and {}", a.1, b.1);
+            }
+        }
+    }
+
+    #[test]
+    fn new_game_hides_the_word() {
+        let game = game("cat");
+        assert_eq!(game.word, "CAT");
+        assert_eq!(game.in_progress_word(), "___");
+        assert_eq!(game.get_game_state(), None);
+    }
+
+    #[test]
+    fn refused_words_give_the_reason() {
+        let normal = DifficultyLevel::get_normal();
+        let new = |word: &str| Game::new(word.to_string(), normal.clone()).err();
+        assert_eq!(new(""), Some(GameError::EmptyWord));
+        assert_eq!(new("   "), Some(GameError::EmptyWord));
+        assert_eq!(new("C@T"), Some(GameError::InvalidCharacter('@')));
+        assert_eq!(new("QXZQ"), Some(GameError::NotInDictionary));
+        let short = DifficultyLevel::new("Short".to_string(), normal.0.clone(), Some(3));
+        assert_eq!(
+            Game::new("ZEBRA".to_string(), short).err(),
+            Some(GameError::WordTooLong(3))
+        );
+    }
+
+    #[test]
+    fn guess_reports_what_happened() {
+        let mut game = game("ZEBRA");
+        assert_eq!(game.guess('E'), GuessOutcome::Hit(vec![1]));
+        assert_eq!(game.guess('Q'), GuessOutcome::Miss);
+        assert_eq!(game.guess('E'), GuessOutcome::AlreadyGuessed);
+        assert_eq!(game.guess('Q'), GuessOutcome::AlreadyGuessed);
+        assert_eq!(game.guess('?'), GuessOutcome::NotInAlphabet);
+        assert_eq!(game.guess_count, 1);
+        assert_eq!(game.in_progress_word(), "_E___");
+    }
+
+    #[test]
+    fn game_is_won_or_lost() {
+        let mut won = game("CAT");
+        for c in "CAT".chars() {
+            won.guess(c);
+        }
+        assert_eq!(won.get_game_state(), Some(true));
+        let mut lost = game("CAT");
+        for c in "BDEFGHIJKLMNOPQRSUVWXYZ"
+            .chars()
+            .take(lost.difficulty.lives())
+        {
+            assert_eq!(lost.guess(c), GuessOutcome::Miss);
+        }
+        assert_eq!(lost.get_game_state(), Some(false));
+    }
+
+    #[test]
+    fn hint_reveals_a_letter_for_a_life() {
+        let mut game = game("ZEBRA");
+        assert_eq!(game.hint('Z'), Ok(GuessOutcome::Hit(vec![0])));
+        assert_eq!(game.hints_used, 1);
+        assert_eq!(game.guess_count, 1);
+        assert_eq!(game.hint('Z'), Err(HintError::NotHidden('Z')));
+        assert_eq!(game.hint('Q'), Err(HintError::NotHidden('Q')));
+        assert_eq!(game.most_common_hidden_letter(), Some('E'));
+    }
+
+    #[test]
+    fn hints_are_limited() {
+        let mut game = game("ZEBRA");
+        game.max_hints = 1;
+        game.hint('Z').unwrap();
+        assert_eq!(game.hint('E'), Err(HintError::NoHintsLeft));
+        let mut game = self::game("ZEBRA");
+        game.guess_count = game.difficulty.lives() as i32 - 1;
+        assert_eq!(game.hint('Z'), Err(HintError::NotEnoughLives));
+        game.guess('Q');
+        assert_eq!(game.hint('Z'), Err(HintError::GameOver));
+    }
+
+    #[test]
+    fn solving_wins_or_costs_the_penalty() {
+        let mut game = game("ZEBRA");
+        assert_eq!(game.solve("  "), SolveOutcome::Empty);
+        assert_eq!(game.solve("zebus"), SolveOutcome::Wrong);
+        assert_eq!(game.guess_count, DEFAULT_SOLVE_PENALTY as i32);
+        assert_eq!(game.solve(" zebra "), SolveOutcome::Correct);
+        assert_eq!(game.get_game_state(), Some(true));
+        assert_eq!(game.solve("zebra"), SolveOutcome::GameOver);
+    }
+
+    #[test]
+    fn solve_penalty_never_draws_more_than_the_hangman() {
+        let mut game = game("ZEBRA");
+        let lives = game.difficulty.lives();
+        game.solve_penalty = u32::MAX;
+        assert_eq!(game.solve("ZEBUS"), SolveOutcome::Wrong);
+        assert_eq!(game.guess_count, lives as i32);
+        assert_eq!(game.get_game_state(), Some(false));
+        let normal = DifficultyLevel::get_normal();
+        assert!(!normal.allows_solve_penalty(0));
+        assert!(normal.allows_solve_penalty(1));
+        assert!(normal.allows_solve_penalty(lives as u32));
+        assert!(!normal.allows_solve_penalty(lives as u32 + 1));
+    }
+
+    #[test]
+    fn phrases_show_spaces_punctuation_and_digits() {
+        let game = game("  don't   panic, 42 ");
+        assert_eq!(game.word, "DON'T PANIC, 42");
+        assert_eq!(game.in_progress_word(), "___'_ _____, 42");
+        assert_eq!(
+            Game::new("4, 2!".to_string(), DifficultyLevel::get_normal()).err(),
+            Some(GameError::NoLetters)
+        );
+        // the words of a phrase do not have to be in the dictionary
+        assert!(Game::new("QXZ QXZ".to_string(), DifficultyLevel::get_normal()).is_ok());
+    }
+
+    #[test]
+    fn phrase_is_won_when_every_letter_is_revealed() {
+        let mut game = game("HI THERE");
+        for c in "HITER".chars() {
+            game.guess(c);
+        }
+        assert_eq!(game.get_game_state(), Some(true));
+    }
+
+    #[test]
+    fn mask_reveals_every_position_of_a_letter() {
+        let mut game = game("LETTER");
+        assert_eq!(game.revealed, vec![false; 6]);
+        assert_eq!(game.guess('T'), GuessOutcome::Hit(vec![2, 3]));
+        assert_eq!(game.guess('E'), GuessOutcome::Hit(vec![1, 4]));
+        assert_eq!(game.revealed, [false, true, true, true, true, false]);
+        assert_eq!(
+            game.progress().collect::<Vec<_>>(),
+            [None, Some('E'), Some('T'), Some('T'), Some('E'), None]
+        );
+    }
+
+    fn phrase(phrase: &str, language: Language, fold_diacritics: bool) -> Game {
+        let mut game =
+            Game::with_language(phrase.to_string(), DifficultyLevel::get_normal(), language)
+                .unwrap();
+        game.set_fold_diacritics(fold_diacritics);
+        game
+    }
+
+    #[test]
+    fn folding_reveals_accented_letters_with_their_base_letter() {
+        let mut game = phrase("idé och te", Language::swedish(), true);
+        assert!(!game.guessable_characters.iter().any(|gc| gc.0 == 'É'));
+        assert_eq!(game.guess('E'), GuessOutcome::Hit(vec![2, 9]));
+        assert_eq!(game.guess('É'), GuessOutcome::AlreadyGuessed);
+        assert_eq!(game.solve("IDE OCH TE"), SolveOutcome::Correct);
+    }
+
+    #[test]
+    fn strict_mode_keeps_accented_letters_apart() {
+        let mut game = phrase("idé och te", Language::swedish(), false);
+        assert!(game.guessable_characters.iter().any(|gc| gc.0 == 'É'));
+        assert_eq!(game.guess('E'), GuessOutcome::Hit(vec![9]));
+        assert_eq!(game.guess('É'), GuessOutcome::Hit(vec![2]));
+    }
+
+    #[test]
+    fn english_loanword_letters_always_fold() {
+        let mut game = phrase("café au lait", Language::english(), false);
+        assert_eq!(game.guessable_characters.len(), 26);
+        assert_eq!(game.guess('E'), GuessOutcome::Hit(vec![3]));
+        assert_eq!(game.in_progress_word(), "___É __ ____");
+    }
+}
@@ -0,0 +1,157 @@
+use crate::game::*;
+use serde::{Deserialize, Serialize};
+
+/// number of rounds in a match unless the players choose otherwise
+pub const DEFAULT_BEST_OF: u32 = 3;
+
+/// one word of a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Round {
+    /// index of the player who picked the word
+    pub setter: usize,
+    pub word: String,
+    /// whether the guesser found the word
+    pub guessed: bool,
+    /// drawing elements drawn before the round ended
+    pub misses: i32,
+}
+
+/// two named players taking turns at setting and guessing the word on the same screen,
+/// the guesser scores a point by finding the word and the setter by hanging the guesser
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Match {
+    pub players: [String; 2],
+    /// the most rounds that are played, the match ends early once it can not be caught up
+    pub best_of: u32,
+    pub rounds: Vec<Round>,
+}
+impl Match {
+    /// starts a match, blank names are replaced with the number of the player
+    pub fn new(players: [String; 2], best_of: u32) -> Match {
+        let mut players = players.map(|name| name.trim().to_string());
+        for (i, name) in players.iter_mut().enumerate() {
+            if name.is_empty() {
+                *name = format!("Player {}", i + 1);
+            }
+        }
+        Match {
+            players,
+            best_of: best_of.max(1),
+            rounds: Vec::new(),
+        }
+    }
+    /// index of the player who picks the word of the next round, the roles swap every round
+    pub fn setter(&self) -> usize {
+        self.rounds.len() % 2
+    }
+    /// index of the player who guesses the word of the next round
+    pub fn guesser(&self) -> usize {
+        1 - self.setter()
+    }
+    /// number of the next round, counting from 1
+    pub fn round_number(&self) -> usize {
+        self.rounds.len() + 1
+    }
+    /// adds a finished game as the next round, returns false if it is not over or the match is
+    pub fn record(&mut self, game: &Game) -> bool {
+        let guessed = match game.get_game_state() {
+            Some(won) if !self.is_over() => won,
+            _ => return false,
+        };
+        self.rounds.push(Round {
+            setter: self.setter(),
+            word: game.word.clone(),
+            guessed,
+            misses: game.guess_count,
+        });
+        true
+    }
+    /// the points of both players
+    pub fn scores(&self) -> [u32; 2] {
+        let mut scores = [0, 0];
+        for round in &self.rounds {
+            let scorer = if round.guessed {
+                1 - round.setter
+            } else {
+                round.setter
+            };
+            scores[scorer] += 1;
+        }
+        scores
+    }
+    /// whether every round has been played or one player has more than half of the points
+    pub fn is_over(&self) -> bool {
+        let needed = self.best_of / 2 + 1;
+        self.rounds.len() as u32 >= self.best_of || self.scores().iter().any(|s| *s >= needed)
+    }
+    /// index of the player who won the match, None while it is played or if it is drawn
+    pub fn winner(&self) -> Option<usize> {
+        let [first, second] = self.scores();
+        match (self.is_over(), first.cmp(&second)) {
+            (true, std::cmp::Ordering::Greater) => Some(0),
+            (true, std::cmp::Ordering::Less) => Some(1),
+            _ => None,
+        }
+    }
+    /// a one line summary of the score, such as "Alice 2 - 1 Bob"
+    pub fn scoreboard(&self) -> String {
+        let [first, second] = self.scores();
+        format!("{} {first} - {second} {}", self.players[0], self.players[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::finished_game;
+
+    fn round(guessed: bool) -> Game {
+        finished_game("CAT", guessed)
+    }
+
+    #[test]
+    fn blank_names_are_numbered() {
+        let new_match = Match::new([" Ann ".to_string(), "  ".to_string()], 0);
+        assert_eq!(new_match.players, ["Ann", "Player 2"]);
+        assert_eq!(new_match.best_of, 1);
+    }
+
+    #[test]
+    fn roles_swap_and_points_go_to_the_round_winner() {
+        let mut hot_seat = Match::new(["Ann".to_string(), "Bob".to_string()], 5);
+        assert_eq!((hot_seat.setter(), hot_seat.guesser()), (0, 1));
+        // Bob finds the word set by Ann
+        assert!(hot_seat.record(&round(true)));
+        assert_eq!((hot_seat.setter(), hot_seat.guesser()), (1, 0));
+        // Ann is hanged on the word set by Bob
+        assert!(hot_seat.record(&round(false)));
+        assert_eq!(hot_seat.scores(), [0, 2]);
+        assert_eq!(hot_seat.round_number(), 3);
+        assert_eq!(hot_seat.scoreboard(), "Ann 0 - 2 Bob");
+        assert!(!hot_seat.is_over());
+        assert_eq!(hot_seat.winner(), None);
+    }
+
+    #[test]
+    fn match_ends_once_it_can_not_be_caught_up() {
+        let mut hot_seat = Match::new(["Ann".to_string(), "Bob".to_string()], 3);
+        let unfinished = Game::new("CAT".to_string(), DifficultyLevel::get_normal()).unwrap();
+        assert!(!hot_seat.record(&unfinished));
+        hot_seat.record(&round(true));
+        hot_seat.record(&round(false));
+        assert!(hot_seat.is_over());
+        assert_eq!(hot_seat.winner(), Some(1));
+        assert!(!hot_seat.record(&round(true)));
+        assert_eq!(hot_seat.rounds.len(), 2);
+    }
+
+    #[test]
+    fn even_match_is_drawn() {
+        let mut hot_seat = Match::new(["Ann".to_string(), "Bob".to_string()], 2);
+        hot_seat.record(&round(true));
+        hot_seat.record(&round(true));
+        assert!(hot_seat.is_over());
+        assert_eq!(hot_seat.scores(), [1, 1]);
+        assert_eq!(hot_seat.winner(), None);
+    }
+}
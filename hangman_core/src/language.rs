@@ -0,0 +1,477 @@
+use lazy_static::lazy_static;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+use crate::picker::WordListStats;
+use crate::solver::LengthGroups;
+
+pub const ENGLISH_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+pub const SWEDISH_ALPHABET: &str = "ABCDEÉFGHIJKLMNOPQRSTUVWXYZÅÄÖ";
+pub const GREEK_ALPHABET: &str = "ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ";
+pub const RUSSIAN_ALPHABET: &str = "АБВГДЕЁЖЗИЙКЛМНОПРСТУФХЦЧШЩЪЫЬЭЮЯ";
+/// accented letters of loanwords and the letter they are guessed as,
+/// they are not in the alphabet so they are folded even in strict mode
+const ENGLISH_FOLDING: &[(char, char)] = &[
+    ('À', 'A'),
+    ('Á', 'A'),
+    ('Â', 'A'),
+    ('Ä', 'A'),
+    ('Ç', 'C'),
+    ('È', 'E'),
+    ('É', 'E'),
+    ('Ê', 'E'),
+    ('Ë', 'E'),
+    ('Î', 'I'),
+    ('Ï', 'I'),
+    ('Ñ', 'N'),
+    ('Ó', 'O'),
+    ('Ô', 'O'),
+    ('Ö', 'O'),
+    ('Û', 'U'),
+    ('Ü', 'U'),
+];
+/// å, ä and ö are letters of their own in swedish, only é is an accented e
+const SWEDISH_FOLDING: &[(char, char)] = &[('É', 'E')];
+/// ё is often written as е
+const RUSSIAN_FOLDING: &[(char, char)] = &[('Ё', 'Е')];
+/// greek is written without accents in upper case
+const GREEK_UPPER_CASE: &[(char, char)] = &[
+    ('ά', 'Α'),
+    ('έ', 'Ε'),
+    ('ή', 'Η'),
+    ('ί', 'Ι'),
+    ('ϊ', 'Ι'),
+    ('ΐ', 'Ι'),
+    ('ό', 'Ο'),
+    ('ύ', 'Υ'),
+    ('ϋ', 'Υ'),
+    ('ΰ', 'Υ'),
+    ('ώ', 'Ω'),
+    ('Ά', 'Α'),
+    ('Έ', 'Ε'),
+    ('Ή', 'Η'),
+    ('Ί', 'Ι'),
+    ('Ϊ', 'Ι'),
+    ('Ό', 'Ο'),
+    ('Ύ', 'Υ'),
+    ('Ϋ', 'Υ'),
+    ('Ώ', 'Ω'),
+];
+lazy_static! {
+    pub static ref ENGLISH_WORD_LIST: Arc<WordList> = Arc::new(WordList::from_zstd(
+        include_bytes!("../../assets/english_word_list.txt.zst")
+    ));
+    pub static ref SWEDISH_WORD_LIST: Arc<WordList> = Arc::new(WordList::from_zstd(
+        include_bytes!("../../assets/swedish_word_list.txt.zst")
+    ));
+    pub static ref GREEK_WORD_LIST: Arc<WordList> = Arc::new(WordList::from_zstd(include_bytes!(
+        "../../assets/greek_word_list.txt.zst"
+    )));
+    pub static ref RUSSIAN_WORD_LIST: Arc<WordList> = Arc::new(WordList::from_zstd(
+        include_bytes!("../../assets/russian_word_list.txt.zst")
+    ));
+}
+pub fn is_word_in_dictionary(word: &str) -> bool {
+    ENGLISH_WORD_LIST
+        .binary_search(&word.to_uppercase())
+        .is_ok()
+}
+
+/// the first bytes of every zstd compressed file
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// the reasons a word list file can not be used
+#[derive(Debug)]
+pub enum WordListError {
+    /// the file could not be read
+    Io(PathBuf, io::Error),
+    /// the file looks compressed but could not be decompressed
+    Decompress(PathBuf, io::Error),
+    /// the file is not utf-8 text, line is where the first invalid byte is
+    NotUtf8 { path: PathBuf, line: usize },
+    /// a word has a character that is not a letter of the language, so it could never be guessed
+    NotALetter {
+        path: PathBuf,
+        line: usize,
+        character: char,
+    },
+    /// the file has no words in it
+    Empty(PathBuf),
+}
+impl fmt::Display for WordListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordListError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            WordListError::Decompress(path, e) => {
+                write!(f, "{}: not a valid zstd file, {e}", path.display())
+            }
+            WordListError::NotUtf8 { path, line } => {
+                write!(f, "{}: line {line} is not valid utf-8 text", path.display())
+            }
+            WordListError::NotALetter {
+                path,
+                line,
+                character,
+            } => write!(
+                f,
+                "{}: line {line} has {character}, which is not a letter of the language",
+                path.display()
+            ),
+            WordListError::Empty(path) => write!(f, "{}: has no words", path.display()),
+        }
+    }
+}
+impl Error for WordListError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WordListError::Io(_, e) | WordListError::Decompress(_, e) => Some(e),
+            WordListError::NotUtf8 { .. }
+            | WordListError::NotALetter { .. }
+            | WordListError::Empty(_) => None,
+        }
+    }
+}
+
+/// a sorted list of upper case words, dereferences to the words
+pub struct WordList {
+    words: Vec<String>,
+    /// the word scores used by the picker, calculated the first time they are needed
+    pub(crate) picker_stats: OnceLock<WordListStats>,
+    /// the words grouped for the solver, calculated the first time they are needed
+    pub(crate) solver_groups: OnceLock<LengthGroups>,
+}
+impl WordList {
+    /// create a word list from words that are already sorted and upper case
+    pub fn from_sorted(words: Vec<String>) -> WordList {
+        WordList {
+            words,
+            picker_stats: OnceLock::new(),
+            solver_groups: OnceLock::new(),
+        }
+    }
+    /// decompresses a word list that is embedded in the executable, one word per line
+    fn from_zstd(source: &'static [u8]) -> WordList {
+        let mut decoder = ZstdDecoder::new(source).unwrap();
+        let mut out = String::new();
+        decoder.read_to_string(&mut out).unwrap();
+        WordList::from_sorted(out.lines().map(|s| s.to_string()).collect())
+    }
+    /// reads a plain text or zstd compressed word list with one word per line,
+    /// the words are trimmed, made upper case in the language, sorted and deduplicated,
+    /// a word with a character that is not a letter of the language is refused
+    pub fn load(path: &Path, language: &Language) -> Result<WordList, WordListError> {
+        let mut bytes = fs::read(path).map_err(|e| WordListError::Io(path.to_path_buf(), e))?;
+        if bytes.starts_with(&ZSTD_MAGIC) {
+            bytes = zstd::stream::decode_all(bytes.as_slice())
+                .map_err(|e| WordListError::Decompress(path.to_path_buf(), e))?;
+        }
+        let text = String::from_utf8(bytes).map_err(|e| {
+            let valid = &e.as_bytes()[..e.utf8_error().valid_up_to()];
+            WordListError::NotUtf8 {
+                path: path.to_path_buf(),
+                line: valid.iter().filter(|&&b| b == b'\n').count() + 1,
+            }
+        })?;
+        let mut words: Vec<String> = Vec::new();
+        for (i, line) in text.trim_start_matches('\u{feff}').lines().enumerate() {
+            let word = language.to_uppercase(line.trim());
+            if let Some(character) = word.chars().find(|&c| !language.is_letter(c)) {
+                return Err(WordListError::NotALetter {
+                    path: path.to_path_buf(),
+                    line: i + 1,
+                    character,
+                });
+            }
+            if !word.is_empty() {
+                words.push(word);
+            }
+        }
+        if words.is_empty() {
+            return Err(WordListError::Empty(path.to_path_buf()));
+        }
+        words.sort_unstable();
+        words.dedup();
+        Ok(WordList::from_sorted(words))
+    }
+}
+impl Deref for WordList {
+    type Target = [String];
+    fn deref(&self) -> &[String] {
+        &self.words
+    }
+}
+
+/// the letters, words and case rules of a language
+#[derive(Clone)]
+pub struct Language {
+    name: &'static str,
+    /// short ISO 639-1 name of the language
+    code: &'static str,
+    alphabet: &'static str,
+    /// letters whose upper case is not what `char::to_uppercase` gives
+    upper_case_exceptions: &'static [(char, char)],
+    /// accented letters and the base letter they are guessed as when diacritics are folded
+    folding: &'static [(char, char)],
+    word_list: Arc<WordList>,
+}
+impl Language {
+    pub fn english() -> Language {
+        Language {
+            name: "English",
+            code: "en",
+            alphabet: ENGLISH_ALPHABET,
+            upper_case_exceptions: &[],
+            folding: ENGLISH_FOLDING,
+            word_list: ENGLISH_WORD_LIST.clone(),
+        }
+    }
+    pub fn swedish() -> Language {
+        Language {
+            name: "Svenska",
+            code: "sv",
+            alphabet: SWEDISH_ALPHABET,
+            upper_case_exceptions: &[],
+            folding: SWEDISH_FOLDING,
+            word_list: SWEDISH_WORD_LIST.clone(),
+        }
+    }
+    pub fn greek() -> Language {
+        Language {
+            name: "Ελληνικά",
+            code: "el",
+            alphabet: GREEK_ALPHABET,
+            upper_case_exceptions: GREEK_UPPER_CASE,
+            folding: &[],
+            word_list: GREEK_WORD_LIST.clone(),
+        }
+    }
+    pub fn russian() -> Language {
+        Language {
+            name: "Русский",
+            code: "ru",
+            alphabet: RUSSIAN_ALPHABET,
+            upper_case_exceptions: &[],
+            folding: RUSSIAN_FOLDING,
+            word_list: RUSSIAN_WORD_LIST.clone(),
+        }
+    }
+    /// all built in languages
+    pub fn get_all() -> Vec<Language> {
+        vec![
+            Language::english(),
+            Language::swedish(),
+            Language::greek(),
+            Language::russian(),
+        ]
+    }
+    /// finds a built in language by its name or code, in any case
+    pub fn find(name: &str) -> Option<Language> {
+        let name = name.to_lowercase();
+        Language::get_all()
+            .into_iter()
+            .find(|l| l.code == name || l.name.to_lowercase() == name)
+    }
+    /// the name of the language, in the language itself
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    /// short ISO 639-1 name of the language
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+    /// the letters that can be guessed, in the order they are shown
+    pub fn alphabet(&self) -> &'static str {
+        self.alphabet
+    }
+    /// the letters shown for guessing, the accented letters are left out when they are folded
+    pub fn guessable_alphabet(&self, fold_diacritics: bool) -> String {
+        self.alphabet
+            .chars()
+            .filter(|&c| !fold_diacritics || self.fold_letter(c) == c)
+            .collect()
+    }
+    /// whether any letter of the language is folded into another
+    pub fn has_folding(&self) -> bool {
+        !self.folding.is_empty()
+    }
+    /// the base letter of an accented letter, other letters are returned as they are
+    pub fn fold_letter(&self, c: char) -> char {
+        match self.folding.iter().find(|(accented, _)| *accented == c) {
+            Some((_, base)) => *base,
+            None => c,
+        }
+    }
+    /// the words that can be chosen
+    pub fn word_list(&self) -> &Arc<WordList> {
+        &self.word_list
+    }
+    /// the same language with other words to choose from
+    pub fn with_word_list(self, word_list: WordList) -> Language {
+        Language {
+            word_list: Arc::new(word_list),
+            ..self
+        }
+    }
+    /// the upper case form of a letter
+    pub fn to_uppercase_char(&self, c: char) -> char {
+        match self
+            .upper_case_exceptions
+            .iter()
+            .find(|(lower, _)| *lower == c)
+        {
+            Some((_, upper)) => *upper,
+            None => c.to_uppercase().next().unwrap_or(c),
+        }
+    }
+    /// the upper case form of a text, one character for each character
+    pub fn to_uppercase(&self, text: &str) -> String {
+        text.chars().map(|c| self.to_uppercase_char(c)).collect()
+    }
+    /// whether the letter is in the alphabet, or is an extra letter folded into one
+    pub fn is_letter(&self, c: char) -> bool {
+        self.alphabet.contains(c) || self.is_extra_letter(c)
+    }
+    /// whether the letter can be in a phrase without being in the alphabet,
+    /// like the accented letters of loanwords, it is always guessed as its base letter
+    pub fn is_extra_letter(&self, c: char) -> bool {
+        !self.alphabet.contains(c) && self.folding.iter().any(|(accented, _)| *accented == c)
+    }
+    /// whether the word is in the word list, in any case
+    pub fn is_word_in_dictionary(&self, word: &str) -> bool {
+        self.word_list
+            .binary_search(&self.to_uppercase(word))
+            .is_ok()
+    }
+}
+impl fmt::Debug for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Language")
+            .field("name", &self.name)
+            .field("code", &self.code)
+            .field("alphabet", &self.alphabet)
+            .field("words", &self.word_list.len())
+            .finish()
+    }
+}
+impl PartialEq for Language {
+    fn eq(&self, other: &Language) -> bool {
+        self.code == other.code
+            && self.alphabet == other.alphabet
+            && Arc::ptr_eq(&self.word_list, &other.word_list)
+    }
+}
+/// a language is saved as its code, a custom word list is replaced by the built in one on load
+impl Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code)
+    }
+}
+impl<'de> Deserialize<'de> for Language {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Language, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Language::find(&code).ok_or_else(|| de::Error::custom(format!("unknown language {code}")))
+    }
+}
+impl Default for Language {
+    fn default() -> Language {
+        Language::english()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn finds_languages_by_code_or_name() {
+        assert_eq!(Language::find("sv"), Some(Language::swedish()));
+        assert_eq!(Language::find("SVENSKA"), Some(Language::swedish()));
+        assert_eq!(Language::find("ru").map(|l| l.code()), Some("ru"));
+        assert_eq!(Language::find("klingon"), None);
+    }
+
+    #[test]
+    fn upper_case_follows_the_language() {
+        assert_eq!(Language::greek().to_uppercase("καλημέρα"), "ΚΑΛΗΜΕΡΑ");
+        assert_eq!(Language::swedish().to_uppercase("kärlek"), "KÄRLEK");
+        assert!(Language::english().is_word_in_dictionary("zebra"));
+    }
+
+    #[test]
+    fn built_in_word_lists_are_sorted() {
+        for language in Language::get_all() {
+            let words = language.word_list();
+            assert!(!words.is_empty(), "{} has no words", language.name());
+            assert!(
+                words.windows(2).all(|w| w[0] < w[1]),
+                "{} is not sorted",
+                language.name()
+            );
+        }
+    }
+
+    fn load(name: &str, bytes: &[u8]) -> Result<WordList, WordListError> {
+        let path = temp_path(name);
+        fs::write(&path, bytes).unwrap();
+        let result = WordList::load(&path, &Language::english());
+        fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn loads_plain_text_word_lists() {
+        let words = load(
+            "plain.txt",
+            "\u{feff}zebra\r\n  cat \n\nZebra\nnaïve\n".as_bytes(),
+        )
+        .unwrap();
+        // accented letters of loanwords are guessed as their base letter, so they are kept
+        assert_eq!(&words[..], ["CAT", "NAÏVE", "ZEBRA"]);
+    }
+
+    #[test]
+    fn loads_zstd_word_lists() {
+        let compressed = zstd::stream::encode_all("dog\ncat\n".as_bytes(), 0).unwrap();
+        let words = load("compressed.zst", &compressed).unwrap();
+        assert_eq!(&words[..], ["CAT", "DOG"]);
+        let mut broken = ZSTD_MAGIC.to_vec();
+        broken.extend_from_slice(b"not zstd");
+        assert!(matches!(
+            load("broken.zst", &broken),
+            Err(WordListError::Decompress(..))
+        ));
+    }
+
+    #[test]
+    fn refuses_unusable_word_lists() {
+        assert!(matches!(
+            load("empty.txt", b" \n\n"),
+            Err(WordListError::Empty(_))
+        ));
+        assert!(matches!(
+            load("binary.txt", b"cat\ndog\n\xff\n"),
+            Err(WordListError::NotUtf8 { line: 3, .. })
+        ));
+        assert!(matches!(
+            load("apostrophe.txt", b"cat\n\ndog's\n"),
+            Err(WordListError::NotALetter {
+                line: 3,
+                character: '\'',
+                ..
+            })
+        ));
+        let missing = temp_path("missing.txt");
+        assert!(matches!(
+            WordList::load(&missing, &Language::english()),
+            Err(WordListError::Io(..))
+        ));
+    }
+}
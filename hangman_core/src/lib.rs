@@ -0,0 +1,15 @@
+//! the rules of hangman, shared between all of the frontends
+pub mod config;
+pub mod game;
+pub mod hotseat;
+pub mod language;
+pub mod network;
+pub mod picker;
+pub mod replay;
+pub mod save;
+pub mod solver;
+pub mod stats;
+#[cfg(test)]
+mod testing;
+pub use game::*;
+pub use language::*;
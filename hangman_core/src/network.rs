@@ -0,0 +1,452 @@
+use crate::game::*;
+use crate::language::Language;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+/// version of the messages, both sides have to use the same one
+pub const PROTOCOL_VERSION: u32 = 2;
+/// the port used when an address has none
+pub const DEFAULT_PORT: u16 = 7878;
+/// how long to wait for the hello of the other side
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// the longest line accepted as a message, a finished game with a long phrase is well below it
+pub const MAX_MESSAGE_LENGTH: usize = 64 * 1024;
+
+/// everything sent between the host and the guest, as one line of json each
+///
+/// both sides start with a hello, then the host sets the word and the guest guesses it.
+/// the host keeps the game and answers every move with what the guest may see of it,
+/// the word is only sent once the game is over
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// the first message of both sides
+    Hello { version: u32, name: String },
+    /// the host is picking the next word
+    Setting,
+    /// the game as the guest sees it on the host, sent whenever it changes and after every move
+    State { view: Box<GuestView> },
+    /// the finished game with its word, sent instead of the state once the game is over
+    GameOver { game: Box<Game> },
+    /// the guest guesses a letter
+    Guess { letter: char },
+    /// the guest asks for a hint
+    Hint,
+    /// the guest guesses the whole word
+    Solve { attempt: String },
+    /// the player left
+    Bye,
+}
+
+/// what the guest may see of a game that is being played, everything but the hidden letters
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GuestView {
+    /// each character of the word, None while it is hidden
+    pub progress: Vec<Option<char>>,
+    /// the letters that can be guessed and whether they have been
+    pub guessable_characters: Vec<GuessableChar>,
+    pub guess_count: i32,
+    pub hints_used: u32,
+    pub max_hints: u32,
+    pub solve_penalty: u32,
+    pub difficulty: DifficultyLevel,
+    pub language: Language,
+    pub fold_diacritics: bool,
+}
+impl GuestView {
+    pub fn new(game: &Game) -> GuestView {
+        GuestView {
+            progress: game.progress().collect(),
+            guessable_characters: game.guessable_characters.clone(),
+            guess_count: game.guess_count,
+            hints_used: game.hints_used,
+            max_hints: game.max_hints,
+            solve_penalty: game.solve_penalty,
+            difficulty: game.difficulty.clone(),
+            language: game.language.clone(),
+            fold_diacritics: game.fold_diacritics,
+        }
+    }
+    /// a game to show the view with, hidden letters are _ in its word so it is only for showing
+    pub fn to_game(&self) -> Game {
+        Game {
+            word: self.progress.iter().map(|c| c.unwrap_or('_')).collect(),
+            revealed: self.progress.iter().map(Option::is_some).collect(),
+            guessable_characters: self.guessable_characters.clone(),
+            guess_count: self.guess_count,
+            difficulty: self.difficulty.clone(),
+            hints_used: self.hints_used,
+            max_hints: self.max_hints,
+            solve_penalty: self.solve_penalty,
+            language: self.language.clone(),
+            fold_diacritics: self.fold_diacritics,
+        }
+    }
+}
+
+/// what the host sends the guest about the game, the word only once the game is over
+pub fn game_message(game: &Game) -> Message {
+    match game.get_game_state() {
+        Some(_) => Message::GameOver {
+            game: Box::new(game.clone()),
+        },
+        None => Message::State {
+            view: Box::new(GuestView::new(game)),
+        },
+    }
+}
+
+/// the reasons playing over the network can fail
+#[derive(Debug)]
+pub enum NetworkError {
+    /// the connection could not be made or was lost
+    Io(io::Error),
+    /// a message was not valid json or not a known message
+    Parse(serde_json::Error),
+    /// the other side speaks another version of the protocol
+    VersionMismatch(u32),
+    /// the other side did not start with a hello
+    NoHello,
+    /// the other side closed the connection
+    Disconnected,
+    /// the other side sent a line longer than any message
+    MessageTooLong,
+}
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Io(e) => write!(f, "{e}"),
+            NetworkError::Parse(e) => write!(f, "invalid message, {e}"),
+            NetworkError::VersionMismatch(version) => write!(
+                f,
+                "the other player uses protocol version {version}, this game uses {PROTOCOL_VERSION}"
+            ),
+            NetworkError::NoHello => write!(f, "the other side is not a hangman game"),
+            NetworkError::Disconnected => write!(f, "the other player left"),
+            NetworkError::MessageTooLong => write!(
+                f,
+                "the other side sent a message longer than {MAX_MESSAGE_LENGTH} bytes"
+            ),
+        }
+    }
+}
+impl Error for NetworkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NetworkError::Io(e) => Some(e),
+            NetworkError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl From<io::Error> for NetworkError {
+    fn from(e: io::Error) -> NetworkError {
+        NetworkError::Io(e)
+    }
+}
+impl From<serde_json::Error> for NetworkError {
+    fn from(e: serde_json::Error) -> NetworkError {
+        NetworkError::Parse(e)
+    }
+}
+
+/// turns "host:port" or just "host" into addresses, using the default port if there is none
+pub fn resolve(address: &str) -> Result<Vec<SocketAddr>, NetworkError> {
+    let addresses = match address.to_socket_addrs() {
+        Ok(addresses) => addresses,
+        Err(_) => (address, DEFAULT_PORT).to_socket_addrs()?,
+    };
+    Ok(addresses.collect())
+}
+
+/// waits for a guest to join a hosted game
+pub struct Listener {
+    listener: TcpListener,
+    name: String,
+    /// guests that have connected but not sent their hello yet, and when they connected
+    handshakes: Vec<(Connection, Instant)>,
+}
+impl Listener {
+    /// starts listening on the address, name is the name of the host
+    pub fn bind(address: &str, name: &str) -> Result<Listener, NetworkError> {
+        let listener = TcpListener::bind(&resolve(address)?[..])?;
+        Ok(Listener {
+            listener,
+            name: name.to_string(),
+            handshakes: Vec::new(),
+        })
+    }
+    /// the address guests can join, useful when binding to port 0
+    pub fn local_addr(&self) -> Result<SocketAddr, NetworkError> {
+        Ok(self.listener.local_addr()?)
+    }
+    /// waits until a guest joins
+    pub fn accept(&self) -> Result<Connection, NetworkError> {
+        self.listener.set_nonblocking(false)?;
+        let (stream, _) = self.listener.accept()?;
+        Connection::handshake(stream, &self.name)
+    }
+    /// returns the guest if one has finished joining since the last call, without waiting,
+    /// an error means one guest could not join and the listener keeps waiting for others
+    pub fn poll(&mut self) -> Result<Option<Connection>, NetworkError> {
+        self.listener.set_nonblocking(true)?;
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    let mut connection = Connection::new(stream)?;
+                    connection.send_hello(&self.name)?;
+                    self.handshakes.push((connection, Instant::now()));
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        for i in 0..self.handshakes.len() {
+            let (connection, connected) = &mut self.handshakes[i];
+            let result = match connection.poll() {
+                Ok(Some(message)) => connection.receive_hello(message),
+                Ok(None) if connected.elapsed() < HANDSHAKE_TIMEOUT => continue,
+                Ok(None) => Err(NetworkError::NoHello),
+                Err(e) => Err(e),
+            };
+            let (connection, _) = self.handshakes.remove(i);
+            return result.map(|()| Some(connection));
+        }
+        Ok(None)
+    }
+}
+
+/// a connection to the other player after the hellos have been exchanged
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    /// the part of a message that has arrived so far
+    pending: Vec<u8>,
+    peer_name: String,
+}
+impl Connection {
+    /// joins the game hosted at the address, name is the name of the guest
+    pub fn join(address: &str, name: &str) -> Result<Connection, NetworkError> {
+        let stream = TcpStream::connect(&resolve(address)?[..])?;
+        Connection::handshake(stream, name)
+    }
+    /// a connection that has not exchanged hellos yet
+    fn new(stream: TcpStream) -> Result<Connection, NetworkError> {
+        stream.set_nodelay(true)?;
+        Ok(Connection {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            pending: Vec::new(),
+            peer_name: String::new(),
+        })
+    }
+    /// exchanges hellos and checks that both sides use the same protocol, waiting for the other side
+    fn handshake(stream: TcpStream, name: &str) -> Result<Connection, NetworkError> {
+        let mut connection = Connection::new(stream)?;
+        connection
+            .writer
+            .set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        connection.send_hello(name)?;
+        let message = connection.receive()?;
+        connection.receive_hello(message)?;
+        connection.writer.set_read_timeout(None)?;
+        Ok(connection)
+    }
+    fn send_hello(&mut self, name: &str) -> Result<(), NetworkError> {
+        self.send(&Message::Hello {
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+        })
+    }
+    /// checks that the first message of the other side is a hello of the same protocol
+    fn receive_hello(&mut self, message: Message) -> Result<(), NetworkError> {
+        match message {
+            Message::Hello { version, .. } if version != PROTOCOL_VERSION => {
+                Err(NetworkError::VersionMismatch(version))
+            }
+            Message::Hello { name, .. } => {
+                self.peer_name = name;
+                Ok(())
+            }
+            _ => Err(NetworkError::NoHello),
+        }
+    }
+    /// the name the other player gave in their hello
+    pub fn peer_name(&self) -> &str {
+        &self.peer_name
+    }
+    /// sends a message, waiting until it has been written
+    pub fn send(&mut self, message: &Message) -> Result<(), NetworkError> {
+        self.writer.set_nonblocking(false)?;
+        let mut line = serde_json::to_vec(message)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        Ok(())
+    }
+    /// waits for the next message
+    pub fn receive(&mut self) -> Result<Message, NetworkError> {
+        loop {
+            if let Some(message) = self.read_message(true)? {
+                return Ok(message);
+            }
+        }
+    }
+    /// returns the next message if one has arrived, without waiting
+    pub fn poll(&mut self) -> Result<Option<Message>, NetworkError> {
+        self.read_message(false)
+    }
+    fn read_message(&mut self, blocking: bool) -> Result<Option<Message>, NetworkError> {
+        // the reader shares the socket with the writer, so this changes both
+        self.reader.get_ref().set_nonblocking(!blocking)?;
+        // a line that never ends can not fill up the memory
+        let room = (MAX_MESSAGE_LENGTH - self.pending.len()) as u64;
+        match self
+            .reader
+            .by_ref()
+            .take(room)
+            .read_until(b'\n', &mut self.pending)
+        {
+            Ok(_) if self.pending.last() == Some(&b'\n') => {}
+            Ok(_) if self.pending.len() >= MAX_MESSAGE_LENGTH => {
+                return Err(NetworkError::MessageTooLong)
+            }
+            // the connection closed without finishing the line
+            Ok(_) => return Err(NetworkError::Disconnected),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock && !blocking => return Ok(None),
+            // only the hello is read with a timeout
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                return Err(NetworkError::NoHello)
+            }
+            Err(e) => return Err(e.into()),
+        }
+        let line = std::mem::take(&mut self.pending);
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&line)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    /// hosts on a free local port and returns the listener and its address
+    fn listen() -> (Listener, String) {
+        let listener = Listener::bind("127.0.0.1:0", "Host").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        (listener, address)
+    }
+
+    #[test]
+    fn handshake_exchanges_names() {
+        let (listener, address) = listen();
+        let guest = thread::spawn(move || Connection::join(&address, "Guest").unwrap());
+        let host = listener.accept().unwrap();
+        let guest = guest.join().unwrap();
+        assert_eq!(host.peer_name(), "Guest");
+        assert_eq!(guest.peer_name(), "Host");
+    }
+
+    #[test]
+    fn other_protocol_version_is_turned_away() {
+        let (mut listener, address) = listen();
+        let mut stream = TcpStream::connect(&address).unwrap();
+        let hello = format!(
+            "{{\"type\":\"hello\",\"version\":{},\"name\":\"Old\"}}\n",
+            PROTOCOL_VERSION + 1
+        );
+        stream.write_all(hello.as_bytes()).unwrap();
+        let result = loop {
+            match listener.poll() {
+                Ok(None) => thread::sleep(Duration::from_millis(5)),
+                result => break result,
+            }
+        };
+        assert!(
+            matches!(result, Err(NetworkError::VersionMismatch(v)) if v == PROTOCOL_VERSION + 1)
+        );
+        // the listener keeps waiting for other guests
+        let guest = thread::spawn(move || Connection::join(&address, "Guest").unwrap());
+        let host = loop {
+            if let Some(connection) = listener.poll().unwrap() {
+                break connection;
+            }
+            thread::sleep(Duration::from_millis(5));
+        };
+        assert_eq!(host.peer_name(), "Guest");
+        guest.join().unwrap();
+    }
+
+    #[test]
+    fn guess_is_answered_with_a_view_without_the_word() {
+        let (listener, address) = listen();
+        let guest = thread::spawn(move || {
+            let mut connection = Connection::join(&address, "Guest").unwrap();
+            connection.send(&Message::Guess { letter: 'A' }).unwrap();
+            connection.receive().unwrap()
+        });
+        let mut host = listener.accept().unwrap();
+        let mut game = Game::new("CAT".to_string(), DifficultyLevel::get_normal()).unwrap();
+        match host.receive().unwrap() {
+            Message::Guess { letter } => game.guess(letter),
+            _ => panic!("expected a guess"),
+        };
+        host.send(&game_message(&game)).unwrap();
+        match guest.join().unwrap() {
+            Message::State { view } => {
+                assert_eq!(view.progress, vec![None, Some('A'), None]);
+                assert_eq!(view.to_game().in_progress_word(), "_A_");
+            }
+            _ => panic!("expected the state"),
+        }
+    }
+
+    #[test]
+    fn finished_game_is_sent_with_the_word() {
+        let mut game = Game::new("CAT".to_string(), DifficultyLevel::get_normal()).unwrap();
+        assert!(matches!(game_message(&game), Message::State { .. }));
+        game.solve("cat");
+        match game_message(&game) {
+            Message::GameOver { game } => assert_eq!(game.word, "CAT"),
+            _ => panic!("expected the game over"),
+        }
+    }
+
+    #[test]
+    fn bye_and_closing_end_the_game() {
+        let (listener, address) = listen();
+        let guest = thread::spawn(move || {
+            let mut connection = Connection::join(&address, "Guest").unwrap();
+            connection.send(&Message::Bye).unwrap();
+        });
+        let mut host = listener.accept().unwrap();
+        guest.join().unwrap();
+        assert!(matches!(host.receive().unwrap(), Message::Bye));
+        assert!(matches!(host.receive(), Err(NetworkError::Disconnected)));
+    }
+
+    #[test]
+    fn endless_line_is_refused() {
+        let (listener, address) = listen();
+        let guest = thread::spawn(move || {
+            let mut connection = Connection::join(&address, "Guest").unwrap();
+            let line = vec![b'x'; MAX_MESSAGE_LENGTH + 1];
+            // the host may stop reading before all of it has been written
+            let _ = connection.writer.write_all(&line);
+        });
+        let mut host = listener.accept().unwrap();
+        assert!(matches!(host.receive(), Err(NetworkError::MessageTooLong)));
+        guest.join().unwrap();
+    }
+}
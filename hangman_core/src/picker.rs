@@ -0,0 +1,363 @@
+use crate::game::*;
+use crate::language::*;
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// the letters of the word without repeats, in the order they first appear
+fn unique_letters(word: &str) -> Vec<char> {
+    let mut letters: Vec<char> = Vec::new();
+    for c in word.chars() {
+        if !letters.contains(&c) {
+            letters.push(c);
+        }
+    }
+    letters
+}
+
+/// the shape of the word with each letter replaced by the order it first appeared in,
+/// e.g. both LETTER and BETTER become [0, 1, 2, 2, 1, 3]
+fn letter_pattern(word: &str) -> Vec<usize> {
+    let letters = unique_letters(word);
+    word.chars()
+        .map(|c| letters.iter().position(|&l| l == c).unwrap_or(0))
+        .collect()
+}
+
+/// the measurements that make a word easy or hard to guess
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordScore {
+    /// number of letters in the word
+    pub length: usize,
+    /// number of different letters in the word
+    pub unique_letters: usize,
+    /// how uncommon the letters of the word are, from 0 to 1
+    pub rarity: f64,
+    /// number of other dictionary words with the same letter pattern
+    pub pattern_matches: usize,
+    /// the combined score, from 0 for the easiest words to 100 for the hardest
+    pub difficulty: f64,
+}
+
+/// the statistics of a word list that word scores are based on
+pub(crate) struct WordListStats {
+    /// fraction of the words that contain each letter of the alphabet
+    letter_frequency: HashMap<char, f64>,
+    /// number of words that have each repeated letter pattern
+    pattern_count: HashMap<Vec<usize>, usize>,
+    max_pattern_count: usize,
+    /// the difficulty score of every word, in the same order as the word list
+    word_difficulty: Vec<f64>,
+    /// the scores a third and two thirds of the way through the sorted scores,
+    /// where the medium and the hard band start
+    band_limits: (f64, f64),
+}
+impl WordListStats {
+    fn new(language: &Language) -> WordListStats {
+        let words = language.word_list();
+        let mut letter_counts: HashMap<char, usize> =
+            language.alphabet().chars().map(|c| (c, 0)).collect();
+        let mut pattern_count = HashMap::new();
+        for word in words.iter() {
+            for c in unique_letters(word) {
+                if let Some(count) = letter_counts.get_mut(&c) {
+                    *count += 1;
+                }
+            }
+            *pattern_count.entry(letter_pattern(word)).or_insert(0) += 1;
+        }
+        let mut stats = WordListStats {
+            letter_frequency: letter_counts
+                .into_iter()
+                .map(|(c, count)| (c, count as f64 / words.len().max(1) as f64))
+                .collect(),
+            max_pattern_count: pattern_count.values().copied().max().unwrap_or(1),
+            pattern_count,
+            word_difficulty: Vec::new(),
+            band_limits: (0.0, 100.0),
+        };
+        stats.word_difficulty = words
+            .iter()
+            .map(|word| stats.score(word).difficulty)
+            .collect();
+        // the bands split every word list into thirds, as the scores of each language spread differently
+        let mut sorted = stats.word_difficulty.clone();
+        sorted.sort_by(f64::total_cmp);
+        let percentile = |p: usize| sorted.get(sorted.len() * p / 100).copied();
+        if let (Some(medium_min), Some(hard_min)) = (percentile(33), percentile(66)) {
+            stats.band_limits = (medium_min, hard_min);
+        }
+        stats
+    }
+    fn score(&self, word: &str) -> WordScore {
+        let length = word.chars().count();
+        let letters = unique_letters(word);
+        let rarity = if letters.is_empty() {
+            0.0
+        } else {
+            letters
+                .iter()
+                .map(|c| 1.0 - self.letter_frequency.get(c).copied().unwrap_or(0.0))
+                .sum::<f64>()
+                / letters.len() as f64
+        };
+        let pattern_matches = self
+            .pattern_count
+            .get(&letter_pattern(word))
+            .copied()
+            .unwrap_or(0)
+            .saturating_sub(1);
+        // every part is scaled to 0..=1 where 1 is the hardest
+        let shortness = ((12.0 - length as f64) / 10.0).clamp(0.0, 1.0);
+        let uniqueness = if length == 0 {
+            0.0
+        } else {
+            letters.len() as f64 / length as f64
+        };
+        let ambiguity =
+            (1.0 + pattern_matches as f64).ln() / (1.0 + self.max_pattern_count as f64).ln();
+        let difficulty =
+            100.0 * (0.25 * shortness + 0.2 * uniqueness + 0.3 * rarity + 0.25 * ambiguity);
+        WordScore {
+            length,
+            unique_letters: letters.len(),
+            rarity,
+            pattern_matches,
+            difficulty,
+        }
+    }
+}
+
+/// the statistics of the word list of the language
+fn stats(language: &Language) -> &WordListStats {
+    language
+        .word_list()
+        .picker_stats
+        .get_or_init(|| WordListStats::new(language))
+}
+
+/// scores how hard a word is to guess compared to the other words of the language,
+/// short words with many different, rare letters and many look-alikes are the hardest
+pub fn score_word(language: &Language, word: &str) -> WordScore {
+    stats(language).score(&language.to_uppercase(word))
+}
+
+/// ranges of word difficulty scores to pick words from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WordBand {
+    Any,
+    Easy,
+    Medium,
+    Hard,
+}
+impl WordBand {
+    /// all bands, in the order they are cycled through
+    pub fn get_all() -> [WordBand; 4] {
+        [
+            WordBand::Any,
+            WordBand::Easy,
+            WordBand::Medium,
+            WordBand::Hard,
+        ]
+    }
+    /// the display name of the band
+    pub fn name(&self) -> &'static str {
+        match self {
+            WordBand::Any => "Any",
+            WordBand::Easy => "Easy",
+            WordBand::Medium => "Medium",
+            WordBand::Hard => "Hard",
+        }
+    }
+    /// the word difficulty scores that belong to the band in the word list of the language,
+    /// each band other than any holds about a third of the words and every score is in one of them
+    pub fn range(&self, language: &Language) -> Range<f64> {
+        let (medium_min, hard_min) = stats(language).band_limits;
+        match self {
+            WordBand::Any => 0.0..f64::INFINITY,
+            WordBand::Easy => 0.0..medium_min,
+            WordBand::Medium => medium_min..hard_min,
+            WordBand::Hard => hard_min..f64::INFINITY,
+        }
+    }
+}
+
+/// whether the dictionary word can be the secret word at the difficulty level
+fn is_playable(word: &str, language: &Language, difficulty: &DifficultyLevel) -> bool {
+    word.chars().all(|c| language.is_letter(c))
+        && difficulty
+            .max_word_length()
+            .is_none_or(|max| word.chars().count() <= max)
+}
+
+/// picks a random word from the dictionary that can be played at the difficulty level
+pub fn random_word<'a, R: Rng + ?Sized>(
+    rng: &mut R,
+    language: &'a Language,
+    difficulty: &DifficultyLevel,
+) -> Option<&'a str> {
+    random_word_in_band(rng, language, difficulty, WordBand::Any.range(language))
+}
+
+/// picks a random word from the dictionary that can be played at the difficulty level
+/// and has a word difficulty score within the band, or the playable word closest to the band
+/// when none is in it
+pub fn random_word_in_band<'a, R: Rng + ?Sized>(
+    rng: &mut R,
+    language: &'a Language,
+    difficulty: &DifficultyLevel,
+    band: Range<f64>,
+) -> Option<&'a str> {
+    let playable =
+        || scored_words(language).filter(|(word, _)| is_playable(word, language, difficulty));
+    let distance = |score: f64| (band.start - score).max(score - band.end).max(0.0);
+    playable()
+        .filter(|(_, score)| band.contains(score))
+        .choose(rng)
+        .or_else(|| playable().min_by(|a, b| distance(a.1).total_cmp(&distance(b.1))))
+        .map(|(word, _)| word)
+}
+
+/// starts a game with a random word from the dictionary, for playing without a setter
+pub fn random_game<R: Rng + ?Sized>(
+    rng: &mut R,
+    language: &Language,
+    difficulty: &DifficultyLevel,
+) -> Option<Game> {
+    random_game_in_band(rng, language, difficulty, WordBand::Any.range(language))
+}
+
+/// starts a game with a random word from the dictionary whose difficulty score is within the band
+pub fn random_game_in_band<R: Rng + ?Sized>(
+    rng: &mut R,
+    language: &Language,
+    difficulty: &DifficultyLevel,
+    band: Range<f64>,
+) -> Option<Game> {
+    let word = random_word_in_band(rng, language, difficulty, band)?;
+    Game::with_language(word.to_string(), difficulty.clone(), language.clone()).ok()
+}
+
+/// every dictionary word of the language with its word difficulty score
+pub fn scored_words(language: &Language) -> impl Iterator<Item = (&str, f64)> {
+    language
+        .word_list()
+        .iter()
+        .map(String::as_str)
+        .zip(stats(language).word_difficulty.iter().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn language(words: &[&str]) -> Language {
+        let words = words.iter().map(|w| w.to_string()).collect();
+        Language::english().with_word_list(WordList::from_sorted(words))
+    }
+
+    #[test]
+    fn words_with_the_same_shape_share_a_pattern() {
+        assert_eq!(letter_pattern("LETTER"), vec![0, 1, 2, 2, 1, 3]);
+        assert_eq!(letter_pattern("LETTER"), letter_pattern("BETTER"));
+        assert_eq!(unique_letters("LETTER"), vec!['L', 'E', 'T', 'R']);
+    }
+
+    #[test]
+    fn short_words_with_rare_letters_score_harder() {
+        let language = language(&["BATTER", "BETTER", "JAZZ", "LETTER", "SETTER", "TEAS"]);
+        let jazz = score_word(&language, "jazz");
+        assert_eq!(jazz.length, 4);
+        assert_eq!(jazz.unique_letters, 3);
+        let letter = score_word(&language, "LETTER");
+        assert_eq!(letter.pattern_matches, 2);
+        assert!(jazz.rarity > score_word(&language, "TEAS").rarity);
+        for (_, score) in scored_words(&language) {
+            assert!((0.0..=100.0).contains(&score));
+        }
+    }
+
+    #[test]
+    fn random_words_are_playable_and_in_the_band() {
+        let language = language(&["BATTER", "BETTER", "JAZZ", "LETTER", "SETTER", "TEAS"]);
+        let short = DifficultyLevel::new(
+            "Short".to_string(),
+            DifficultyLevel::get_normal().0,
+            Some(4),
+        );
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let word = random_word(&mut rng, &language, &short).unwrap();
+            assert!(word.chars().count() <= 4);
+            let band = WordBand::Hard.range(&language);
+            let word = random_word_in_band(
+                &mut rng,
+                &language,
+                &DifficultyLevel::get_normal(),
+                band.clone(),
+            )
+            .unwrap();
+            assert!(band.contains(&score_word(&language, word).difficulty));
+        }
+        let game = random_game(&mut rng, &language, &short).unwrap();
+        assert!(["JAZZ", "TEAS"].contains(&game.word.as_str()));
+    }
+
+    #[test]
+    fn bands_split_the_word_list_into_thirds() {
+        let language = language(&[
+            "BATTER", "BETTER", "CAT", "DOG", "JAZZ", "LETTER", "QUIZ", "SETTER", "TEAS",
+        ]);
+        for band in [WordBand::Easy, WordBand::Medium, WordBand::Hard] {
+            let range = band.range(&language);
+            let count = scored_words(&language)
+                .filter(|(_, score)| range.contains(score))
+                .count();
+            assert!(
+                (2..=4).contains(&count),
+                "{} has {count} words",
+                band.name()
+            );
+        }
+        assert_eq!(WordBand::Any.range(&language), 0.0..f64::INFINITY);
+    }
+
+    #[test]
+    fn scores_at_the_limits_are_in_one_band() {
+        let language = language(&[
+            "BATTER", "BETTER", "CAT", "DOG", "JAZZ", "LETTER", "QUIZ", "SETTER", "TEAS",
+        ]);
+        let (medium_min, hard_min) = stats(&language).band_limits;
+        let bands = |score: f64| -> Vec<WordBand> {
+            [WordBand::Easy, WordBand::Medium, WordBand::Hard]
+                .into_iter()
+                .filter(|band| band.range(&language).contains(&score))
+                .collect()
+        };
+        assert_eq!(bands(medium_min), [WordBand::Medium]);
+        assert_eq!(bands(hard_min), [WordBand::Hard]);
+        assert_eq!(bands(0.0), [WordBand::Easy]);
+        assert_eq!(bands(100.0), [WordBand::Hard]);
+        for (word, score) in scored_words(&language) {
+            assert_eq!(bands(score).len(), 1, "{word} is in more than one band");
+        }
+    }
+
+    #[test]
+    fn empty_band_falls_back_to_the_closest_word() {
+        let language = language(&["BATTER", "CAT", "JAZZ"]);
+        let hardest = scored_words(&language)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(word, _)| word);
+        let mut rng = StdRng::seed_from_u64(1);
+        let normal = DifficultyLevel::get_normal();
+        assert_eq!(
+            random_word_in_band(&mut rng, &language, &normal, 200.0..300.0),
+            hardest
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use crate::game::*;
+use crate::language::*;
+use crate::save::{self, SaveError};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// version of the replay file layout, bump it when the layout changes
+pub const REPLAY_VERSION: u64 = 1;
+
+/// something the guesser did that changed the game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Move {
+    Guess(char),
+    Hint(char),
+    Solve(String),
+}
+impl Move {
+    /// does the move again on the game
+    pub fn apply(&self, game: &mut Game) {
+        match self {
+            Move::Guess(c) => {
+                game.guess(*c);
+            }
+            Move::Hint(c) => {
+                let _ = game.hint(*c);
+            }
+            Move::Solve(attempt) => {
+                game.solve(attempt);
+            }
+        }
+    }
+}
+
+/// a move and when it was made
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedMove {
+    /// seconds since the start of the game
+    pub time: f64,
+    #[serde(rename = "move")]
+    pub action: Move,
+}
+
+/// the word, rules and moves of a game, enough to play it again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    version: u64,
+    /// when the game started, in seconds since the unix epoch
+    pub started: u64,
+    pub word: String,
+    pub difficulty: DifficultyLevel,
+    pub language: Language,
+    pub fold_diacritics: bool,
+    pub max_hints: u32,
+    pub solve_penalty: u32,
+    pub moves: Vec<TimedMove>,
+}
+impl Replay {
+    /// starts recording a game that has not been played yet
+    pub fn new(game: &Game) -> Replay {
+        Replay {
+            version: REPLAY_VERSION,
+            started: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            word: game.word.clone(),
+            difficulty: game.difficulty.clone(),
+            language: game.language.clone(),
+            fold_diacritics: game.fold_diacritics,
+            max_hints: game.max_hints,
+            solve_penalty: game.solve_penalty,
+            moves: Vec::new(),
+        }
+    }
+    /// adds a move made the given number of seconds into the game
+    pub fn record(&mut self, time: f64, action: Move) {
+        self.moves.push(TimedMove { time, action });
+    }
+    /// the game as it was before the first move
+    pub fn start(&self) -> Game {
+        let mut game = Game::with_valid_word(
+            self.word.clone(),
+            self.difficulty.clone(),
+            self.language.clone(),
+        );
+        game.max_hints = self.max_hints;
+        game.solve_penalty = self.solve_penalty;
+        game.set_fold_diacritics(self.fold_diacritics);
+        game
+    }
+    /// seconds from the start of the game to the last move
+    pub fn duration(&self) -> f64 {
+        self.moves.last().map_or(0.0, |m| m.time)
+    }
+    /// reads a replay from a file
+    pub fn load(path: &Path) -> Result<Replay, SaveError> {
+        let (value, version) = save::read_json(path)?;
+        if version != Some(REPLAY_VERSION) {
+            return Err(SaveError::UnsupportedVersion(path.to_path_buf(), version));
+        }
+        save::from_json(path, value)
+    }
+    /// writes the replay to a file, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        save::write_json(path, self)
+    }
+    /// writes the replay to a new file in the directory, named after when the game started,
+    /// a number is added to the name instead of overwriting a replay from the same second
+    pub fn save_new(&self, dir: &Path) -> Result<PathBuf, SaveError> {
+        fs::create_dir_all(dir).map_err(|e| SaveError::Io(dir.to_path_buf(), e))?;
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| SaveError::Parse(dir.to_path_buf(), e))?;
+        let mut number = 0;
+        loop {
+            let name = match number {
+                0 => format!("replay-{}.json", self.started),
+                n => format!("replay-{}-{n}.json", self.started),
+            };
+            let path = dir.join(name);
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    return match file.write_all(json.as_bytes()) {
+                        Ok(()) => Ok(path),
+                        Err(e) => Err(SaveError::Io(path, e)),
+                    };
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => number += 1,
+                Err(e) => return Err(SaveError::Io(path, e)),
+            }
+        }
+    }
+}
+
+/// the directory replays are kept in, in the user data dir
+pub fn replay_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("hangman").join("replays"))
+}
+
+/// plays the moves of a replay back on a game at their recorded times
+pub struct Playback {
+    replay: Replay,
+    /// index of the next move to play
+    next: usize,
+    /// seconds into the replay
+    time: f64,
+    pub paused: bool,
+    /// how many times faster than recorded the moves are played
+    pub speed: f64,
+}
+impl Playback {
+    /// starts from the beginning, returns the game the moves are played on
+    pub fn new(replay: Replay) -> (Playback, Game) {
+        let game = replay.start();
+        let playback = Playback {
+            replay,
+            next: 0,
+            time: 0.0,
+            paused: false,
+            speed: 1.0,
+        };
+        (playback, game)
+    }
+    /// moves the replay forward by the seconds times the speed, playing the moves that are due
+    pub fn advance(&mut self, game: &mut Game, seconds: f64) {
+        if self.paused {
+            return;
+        }
+        self.time += seconds * self.speed;
+        while self
+            .replay
+            .moves
+            .get(self.next)
+            .is_some_and(|m| m.time <= self.time)
+        {
+            self.step(game);
+        }
+    }
+    /// plays the next move right away, returns false if there are no moves left
+    pub fn step(&mut self, game: &mut Game) -> bool {
+        match self.replay.moves.get(self.next) {
+            Some(m) => {
+                m.action.apply(game);
+                self.time = self.time.max(m.time);
+                self.next += 1;
+                true
+            }
+            None => false,
+        }
+    }
+    /// number of moves played so far
+    pub fn moves_played(&self) -> usize {
+        self.next
+    }
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+    /// whether every move has been played
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.replay.moves.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    fn recorded() -> (Replay, Game) {
+        let mut game = Game::new("ZEBRA".to_string(), DifficultyLevel::get_normal()).unwrap();
+        game.solve_penalty = 3;
+        let mut replay = Replay::new(&game);
+        for (time, action) in [
+            (1.0, Move::Guess('E')),
+            (2.5, Move::Solve("ZEBUS".to_string())),
+            (4.0, Move::Hint('Z')),
+        ] {
+            action.apply(&mut game);
+            replay.record(time, action);
+        }
+        (replay, game)
+    }
+
+    #[test]
+    fn playback_ends_where_the_game_did() {
+        let (replay, game) = recorded();
+        assert_eq!(replay.duration(), 4.0);
+        let (mut playback, mut played) = Playback::new(replay);
+        assert_eq!(played.in_progress_word(), "_____");
+        playback.advance(&mut played, 2.0);
+        assert_eq!(playback.moves_played(), 1);
+        playback.paused = true;
+        playback.advance(&mut played, 10.0);
+        assert_eq!(playback.moves_played(), 1);
+        assert!(playback.step(&mut played));
+        assert!(playback.step(&mut played));
+        assert!(!playback.step(&mut played));
+        assert!(playback.is_finished());
+        assert_eq!(played.revealed, game.revealed);
+        assert_eq!(played.guess_count, game.guess_count);
+        assert_eq!(played.hints_used, 1);
+    }
+
+    #[test]
+    fn replays_from_the_same_second_get_their_own_file() {
+        let dir = temp_path("replays");
+        let (replay, _) = recorded();
+        let first = replay.save_new(&dir).unwrap();
+        let second = replay.save_new(&dir).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            second.file_name().unwrap().to_str().unwrap(),
+            format!("replay-{}-1.json", replay.started)
+        );
+        let loaded = Replay::load(&second).unwrap();
+        assert_eq!(loaded.moves.len(), 3);
+        assert_eq!(loaded.solve_penalty, 3);
+        assert_eq!(loaded.start().word, "ZEBRA");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn other_versions_are_refused() {
+        let dir = temp_path("replay-version");
+        let path = dir.join("replay.json");
+        let (mut replay, _) = recorded();
+        replay.version = REPLAY_VERSION + 1;
+        replay.save(&path).unwrap();
+        assert!(matches!(
+            Replay::load(&path),
+            Err(SaveError::UnsupportedVersion(_, Some(v))) if v == REPLAY_VERSION + 1
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,163 @@
+use crate::game::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// name of the file an unfinished game is saved to
+pub const SAVE_FILE_NAME: &str = "save.json";
+/// version of the save file layout, bump it and add a migration when the layout changes
+pub const SAVE_VERSION: u64 = 1;
+
+/// the layout of a save file, state is whatever the frontend needs to continue the game
+#[derive(Serialize, Deserialize)]
+struct SaveFile<G, S> {
+    version: u64,
+    game: G,
+    state: S,
+}
+
+/// the reasons a save file can not be written or read
+#[derive(Debug)]
+pub enum SaveError {
+    /// the file could not be written or read
+    Io(PathBuf, io::Error),
+    /// the file is not valid json or does not have the expected layout
+    Parse(PathBuf, serde_json::Error),
+    /// the file was saved by a newer version of the game, or has no version
+    UnsupportedVersion(PathBuf, Option<u64>),
+}
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            SaveError::Parse(path, e) => write!(f, "{}: {e}", path.display()),
+            SaveError::UnsupportedVersion(path, Some(version)) => {
+                write!(
+                    f,
+                    "{}: save version {version} is not supported",
+                    path.display()
+                )
+            }
+            SaveError::UnsupportedVersion(path, None) => {
+                write!(f, "{}: the save has no version", path.display())
+            }
+        }
+    }
+}
+impl Error for SaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SaveError::Io(_, e) => Some(e),
+            SaveError::Parse(_, e) => Some(e),
+            SaveError::UnsupportedVersion(..) => None,
+        }
+    }
+}
+
+/// where the unfinished game is saved, in the user data dir
+pub fn save_file_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("hangman").join(SAVE_FILE_NAME))
+}
+
+/// writes a value to a file as json, creating its directory if needed
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), SaveError> {
+    let io_error = |e| SaveError::Io(path.to_path_buf(), e);
+    let json =
+        serde_json::to_string_pretty(value).map_err(|e| SaveError::Parse(path.to_path_buf(), e))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, json).map_err(io_error)
+}
+
+/// reads a json file and the version field at its top level
+pub(crate) fn read_json(path: &Path) -> Result<(Value, Option<u64>), SaveError> {
+    let source = fs::read_to_string(path).map_err(|e| SaveError::Io(path.to_path_buf(), e))?;
+    let value: Value =
+        serde_json::from_str(&source).map_err(|e| SaveError::Parse(path.to_path_buf(), e))?;
+    let version = value.get("version").and_then(Value::as_u64);
+    Ok((value, version))
+}
+
+/// turns json read from a file into a value of the expected layout
+pub(crate) fn from_json<T: DeserializeOwned>(path: &Path, value: Value) -> Result<T, SaveError> {
+    serde_json::from_value(value).map_err(|e| SaveError::Parse(path.to_path_buf(), e))
+}
+
+/// writes the game and the state of the frontend to the file, creating its directory if needed
+pub fn save_game<S: Serialize>(path: &Path, game: &Game, state: &S) -> Result<(), SaveError> {
+    let save = SaveFile {
+        version: SAVE_VERSION,
+        game,
+        state,
+    };
+    write_json(path, &save)
+}
+
+/// reads a saved game and the state of the frontend, migrating older save versions
+pub fn load_game<S: DeserializeOwned>(path: &Path) -> Result<(Game, S), SaveError> {
+    let (value, version) = read_json(path)?;
+    match version {
+        // older versions are migrated to the current layout here
+        Some(SAVE_VERSION) => {}
+        _ => return Err(SaveError::UnsupportedVersion(path.to_path_buf(), version)),
+    }
+    let save: SaveFile<Game, S> = from_json(path, value)?;
+    Ok((save.game, save.state))
+}
+
+/// removes the saved game, a missing file is not an error
+pub fn delete_save(path: &Path) -> Result<(), SaveError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(SaveError::Io(path.to_path_buf(), e)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn saved_game_loads_the_same() {
+        let path = temp_path("save-round-trip.json");
+        let mut game = Game::new("ZEBRA".to_string(), DifficultyLevel::get_hard()).unwrap();
+        game.guess('E');
+        game.guess('Q');
+        save_game(&path, &game, &"guessing".to_string()).unwrap();
+        let (loaded, state): (Game, String) = load_game(&path).unwrap();
+        assert_eq!(state, "guessing");
+        assert_eq!(loaded.word, game.word);
+        assert_eq!(loaded.revealed, game.revealed);
+        assert_eq!(loaded.guess_count, 1);
+        assert_eq!(loaded.difficulty.name(), "Hard");
+        delete_save(&path).unwrap();
+        assert!(!path.exists());
+        // deleting a save that is gone is not an error
+        delete_save(&path).unwrap();
+    }
+
+    #[test]
+    fn other_versions_are_refused() {
+        let path = temp_path("save-version.json");
+        fs::write(&path, format!("{{\"version\": {}}}", SAVE_VERSION + 1)).unwrap();
+        assert!(matches!(
+            load_game::<()>(&path),
+            Err(SaveError::UnsupportedVersion(_, Some(v))) if v == SAVE_VERSION + 1
+        ));
+        fs::write(&path, "{}").unwrap();
+        assert!(matches!(
+            load_game::<()>(&path),
+            Err(SaveError::UnsupportedVersion(_, None))
+        ));
+        fs::write(&path, "not json").unwrap();
+        assert!(matches!(load_game::<()>(&path), Err(SaveError::Parse(..))));
+        delete_save(&path).unwrap();
+    }
+}
@@ -0,0 +1,233 @@
+use crate::game::*;
+use crate::language::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// the playable dictionary words with the same number of letters
+struct LengthGroup {
+    /// positions of the words in the word list
+    words: Vec<usize>,
+    /// number of words each letter is in
+    letter_counts: HashMap<char, usize>,
+}
+
+/// the playable dictionary words of a word list grouped by their number of letters
+pub(crate) struct LengthGroups(HashMap<usize, LengthGroup>);
+impl LengthGroups {
+    fn new(language: &Language) -> LengthGroups {
+        let words = language.word_list();
+        let mut groups: HashMap<usize, LengthGroup> = HashMap::new();
+        for (i, word) in words.iter().enumerate() {
+            if !word.chars().all(|c| language.is_letter(c)) {
+                continue;
+            }
+            groups
+                .entry(word.chars().count())
+                .or_insert_with(|| LengthGroup {
+                    words: Vec::new(),
+                    letter_counts: HashMap::new(),
+                })
+                .words
+                .push(i);
+        }
+        for group in groups.values_mut() {
+            group.letter_counts =
+                count_letters(group.words.iter().map(|&i| words[i].as_str()), |c| c);
+        }
+        LengthGroups(groups)
+    }
+}
+
+/// the word groups of the language of the game
+fn length_groups(game: &Game) -> &LengthGroups {
+    game.language
+        .word_list()
+        .solver_groups
+        .get_or_init(|| LengthGroups::new(&game.language))
+}
+
+/// counts the number of words each letter is in, after folding it into the letter it is guessed as
+fn count_letters<'a>(
+    words: impl Iterator<Item = &'a str>,
+    fold: impl Fn(char) -> char,
+) -> HashMap<char, usize> {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut seen: Vec<char> = Vec::new();
+    for word in words {
+        seen.clear();
+        for c in word.chars().map(&fold) {
+            if !seen.contains(&c) {
+                seen.push(c);
+                *counts.entry(c).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// the letters that have been guessed
+fn guessed_letters(game: &Game) -> Vec<char> {
+    game.guessable_characters
+        .iter()
+        .filter(|gc| gc.1)
+        .map(|gc| gc.0)
+        .collect()
+}
+
+/// whether the word fits the revealed letters and misses of the game
+fn is_consistent(word: &str, game: &Game, unguessed: &HashSet<char>) -> bool {
+    if word.chars().count() != game.revealed.len() {
+        return false;
+    }
+    word.chars().zip(game.progress()).all(|(w, p)| match p {
+        None => unguessed.contains(&game.guess_letter(w)),
+        Some(p) => w == p,
+    })
+}
+
+/// guesses letters by narrowing the dictionary down to the words that fit the game so far
+pub struct Solver {
+    word_list: Arc<WordList>,
+    /// positions of the dictionary words that could still be the secret word
+    candidates: Vec<usize>,
+    /// the letters that candidates has been narrowed down by
+    guessed: Vec<char>,
+    /// number of letters in the word
+    length: usize,
+}
+impl Solver {
+    /// starts from the dictionary words that fit the game
+    pub fn new(game: &Game) -> Solver {
+        let length = game.revealed.len();
+        let guessed = guessed_letters(game);
+        let word_list = game.language.word_list().clone();
+        let words = length_groups(game)
+            .0
+            .get(&length)
+            .map(|group| group.words.as_slice())
+            .unwrap_or_default();
+        let candidates = if guessed.is_empty() {
+            words.to_vec()
+        } else {
+            let unguessed: HashSet<char> = game
+                .guessable_characters
+                .iter()
+                .filter(|gc| !gc.1)
+                .map(|gc| gc.0)
+                .collect();
+            words
+                .iter()
+                .copied()
+                .filter(|&i| is_consistent(&word_list[i], game, &unguessed))
+                .collect()
+        };
+        Solver {
+            word_list,
+            candidates,
+            guessed,
+            length,
+        }
+    }
+    /// removes the candidates that no longer fit the game, call after each guess
+    pub fn update(&mut self, game: &Game) {
+        let progress: Vec<Option<char>> = game.progress().collect();
+        for c in guessed_letters(game) {
+            if self.guessed.contains(&c) {
+                continue;
+            }
+            self.guessed.push(c);
+            // the letter has to be at exactly the revealed positions, or nowhere on a miss
+            let words = &self.word_list;
+            self.candidates.retain(|&i| {
+                words[i].chars().zip(progress.iter()).all(|(w, p)| {
+                    (game.guess_letter(w) == c) == (p.map(|p| game.guess_letter(p)) == Some(c))
+                })
+            });
+        }
+    }
+    /// the dictionary words that could still be the secret word
+    pub fn candidates(&self) -> impl Iterator<Item = &str> {
+        self.candidates.iter().map(|&i| self.word_list[i].as_str())
+    }
+    /// the unguessed letter found in the most candidates,
+    /// falls back to the whole dictionary if no candidate is left
+    pub fn next_guess(&mut self, game: &Game) -> Option<char> {
+        self.update(game);
+        let group = length_groups(game).0.get(&self.length);
+        // extra letters are folded even in strict mode
+        let folds = game.language.has_folding();
+        // nothing has been ruled out yet, so the counts of the whole group can be reused
+        let counts = match group {
+            Some(group)
+                if self.candidates.is_empty() || self.candidates.len() == group.words.len() =>
+            {
+                if folds {
+                    let words = group.words.iter().map(|&i| self.word_list[i].as_str());
+                    count_letters(words, |c| game.guess_letter(c))
+                } else {
+                    group.letter_counts.clone()
+                }
+            }
+            _ => count_letters(self.candidates(), |c| game.guess_letter(c)),
+        };
+        // the letter that comes first in the alphabet wins a tie
+        game.guessable_characters
+            .iter()
+            .rev()
+            .filter(|gc| !gc.1)
+            .map(|gc| (gc.0, counts.get(&gc.0).copied().unwrap_or(0)))
+            .max_by_key(|(_, count)| *count)
+            .map(|(c, _)| c)
+    }
+}
+
+/// picks the next letter to guess for the game
+pub fn next_guess(game: &Game) -> Option<char> {
+    Solver::new(game).next_guess(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(word: &str, words: &[&str]) -> Game {
+        let words = words.iter().map(|w| w.to_string()).collect();
+        let language = Language::english().with_word_list(WordList::from_sorted(words));
+        Game::with_language(word.to_string(), DifficultyLevel::get_normal(), language).unwrap()
+    }
+
+    #[test]
+    fn candidates_narrow_down_with_each_guess() {
+        let mut game = game("CAT", &["BAT", "CAT", "COT", "DOGS"]);
+        let mut solver = Solver::new(&game);
+        assert_eq!(
+            solver.candidates().collect::<Vec<_>>(),
+            ["BAT", "CAT", "COT"]
+        );
+        game.guess('A');
+        solver.update(&game);
+        assert_eq!(solver.candidates().collect::<Vec<_>>(), ["BAT", "CAT"]);
+        game.guess('B');
+        solver.update(&game);
+        assert_eq!(solver.candidates().collect::<Vec<_>>(), ["CAT"]);
+    }
+
+    #[test]
+    fn guesses_the_most_common_letter_first() {
+        let game = game("CAT", &["BAT", "CAT", "COT", "DOGS"]);
+        // T is in all three words of three letters
+        assert_eq!(next_guess(&game), Some('T'));
+    }
+
+    #[test]
+    fn solver_wins_with_a_small_dictionary() {
+        let mut game = game("COT", &["BAT", "CAT", "COT", "CUT", "HAT"]);
+        let mut solver = Solver::new(&game);
+        while game.get_game_state().is_none() {
+            let guess = solver.next_guess(&game).unwrap();
+            game.guess(guess);
+        }
+        assert_eq!(game.get_game_state(), Some(true));
+        assert!(game.guess_count <= 2);
+    }
+}
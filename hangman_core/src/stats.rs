@@ -0,0 +1,143 @@
+use crate::game::*;
+use crate::save::{self, SaveError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// name of the file the statistics are kept in
+pub const STATS_FILE_NAME: &str = "stats.json";
+/// version of the statistics file layout, bump it when the layout changes
+pub const STATS_VERSION: u64 = 1;
+
+/// the results of every finished game at one difficulty level
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DifficultyStats {
+    pub wins: u32,
+    pub losses: u32,
+    /// number of games won in a row, up to the last game
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// drawing elements drawn over every game, including hints and wrong solve attempts
+    pub total_misses: u64,
+}
+impl DifficultyStats {
+    /// number of finished games
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses
+    }
+    /// the share of the games that were won, 0 to 1
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games().max(1) as f64
+    }
+    /// the mean number of drawing elements drawn per game
+    pub fn average_misses(&self) -> f64 {
+        self.total_misses as f64 / self.games().max(1) as f64
+    }
+}
+
+/// the statistics of the player, kept between runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stats {
+    version: u64,
+    /// the statistics of each difficulty level by its name
+    difficulties: BTreeMap<String, DifficultyStats>,
+}
+impl Default for Stats {
+    fn default() -> Stats {
+        Stats {
+            version: STATS_VERSION,
+            difficulties: BTreeMap::new(),
+        }
+    }
+}
+impl Stats {
+    /// adds a game to the statistics of its difficulty level, returns false if it is not over
+    pub fn record(&mut self, game: &Game) -> bool {
+        let won = match game.get_game_state() {
+            Some(won) => won,
+            None => return false,
+        };
+        let stats = self
+            .difficulties
+            .entry(game.difficulty.name().to_string())
+            .or_default();
+        if won {
+            stats.wins += 1;
+            stats.current_streak += 1;
+            stats.best_streak = stats.best_streak.max(stats.current_streak);
+        } else {
+            stats.losses += 1;
+            stats.current_streak = 0;
+        }
+        stats.total_misses += game.guess_count.max(0) as u64;
+        true
+    }
+    /// the statistics of a difficulty level, None if no game has been finished at it
+    pub fn get(&self, difficulty: &DifficultyLevel) -> Option<&DifficultyStats> {
+        self.difficulties.get(difficulty.name())
+    }
+    /// reads the statistics from a file, a missing file gives empty statistics
+    pub fn load(path: &Path) -> Result<Stats, SaveError> {
+        if !path.is_file() {
+            return Ok(Stats::default());
+        }
+        let (value, version) = save::read_json(path)?;
+        if version != Some(STATS_VERSION) {
+            return Err(SaveError::UnsupportedVersion(path.to_path_buf(), version));
+        }
+        save::from_json(path, value)
+    }
+    /// writes the statistics to a file, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        save::write_json(path, self)
+    }
+}
+
+/// where the statistics are kept, in the user data dir
+pub fn stats_file_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("hangman").join(STATS_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{finished_game, temp_path};
+    use std::fs;
+
+    #[test]
+    fn records_wins_losses_and_streaks() {
+        let mut stats = Stats::default();
+        let normal = DifficultyLevel::get_normal();
+        assert!(stats.get(&normal).is_none());
+        let unfinished = Game::new("CAT".to_string(), normal.clone()).unwrap();
+        assert!(!stats.record(&unfinished));
+        assert!(stats.record(&finished_game("CAT", true)));
+        assert!(stats.record(&finished_game("DOG", true)));
+        assert!(stats.record(&finished_game("ZEBRA", false)));
+        assert!(stats.record(&finished_game("CAT", true)));
+        let normal_stats = stats.get(&normal).unwrap();
+        assert_eq!((normal_stats.wins, normal_stats.losses), (3, 1));
+        assert_eq!(normal_stats.current_streak, 1);
+        assert_eq!(normal_stats.best_streak, 2);
+        assert_eq!(normal_stats.total_misses, normal.lives() as u64);
+        assert_eq!(normal_stats.win_rate(), 0.75);
+        assert!(stats.get(&DifficultyLevel::get_hard()).is_none());
+    }
+
+    #[test]
+    fn statistics_round_trip_and_check_the_version() {
+        let path = temp_path("stats-round-trip.json");
+        assert_eq!(Stats::load(&path).unwrap().difficulties.len(), 0);
+        let mut stats = Stats::default();
+        stats.record(&finished_game("CAT", true));
+        stats.save(&path).unwrap();
+        let loaded = Stats::load(&path).unwrap();
+        assert_eq!(loaded.get(&DifficultyLevel::get_normal()).unwrap().wins, 1);
+        fs::write(&path, "{\"version\": 0, \"difficulties\": {}}").unwrap();
+        assert!(matches!(
+            Stats::load(&path),
+            Err(SaveError::UnsupportedVersion(_, Some(0)))
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,30 @@
+//! helpers shared by the tests of the modules
+use crate::game::*;
+use std::fs;
+use std::path::PathBuf;
+
+/// a path in the temp dir of this test run, the dir is created if needed
+pub fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("hangman-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+/// a finished game of the word at the normal level, won by guessing the letters of the word
+/// or lost by guessing letters that are not in it
+pub fn finished_game(word: &str, won: bool) -> Game {
+    let mut game = Game::new(word.to_string(), DifficultyLevel::get_normal()).unwrap();
+    let letters: Vec<char> = game
+        .guessable_characters
+        .iter()
+        .map(|gc| gc.0)
+        .filter(|&c| game.word.contains(c) == won)
+        .collect();
+    for c in letters {
+        if game.get_game_state().is_some() {
+            break;
+        }
+        game.guess(c);
+    }
+    game
+}
@@ -5,17 +5,28 @@ extern crate piston;
 
 mod rendering;
 mod colors;
+mod fonts;
 use colors::*;
+use fonts::FallbackGlyphCache;
 
-use crate::game::*;
-use opengl_graphics::GlyphCache;
-use opengl_graphics::{GlGraphics, OpenGL, TextureSettings};
+use hangman_core::*;
+use hangman_core::hotseat::{self, Match};
+use hangman_core::network::{self, Connection, Listener, Message};
+use hangman_core::picker::WordBand;
+use hangman_core::replay::{self, Move, Playback, Replay};
+use hangman_core::save;
+use hangman_core::stats::{self, Stats};
+use graphics::character::CharacterCache;
+use opengl_graphics::{GlGraphics, OpenGL};
 use piston::input::{RenderArgs, UpdateArgs};
 use piston::{Button, ButtonArgs, Key, ResizeArgs};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
 use std::cmp::min;
 use std::time::Instant;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum AppState {
     Selecting,
     Guessing,
@@ -25,42 +36,182 @@ pub enum AppState {
 /// logical size of the window to help with positioning elements on the screen
 pub const WINDOW_DOTS: f64 = 1000.0;
 const FONT_SIZE: u32 = 52;
+/// seconds between the guesses of the computer guesser
+const AI_GUESS_DELAY: f64 = 0.7;
+/// the fastest and slowest a replay can be played back
+const PLAYBACK_SPEEDS: [f64; 2] = [0.25, 16.0];
+/// the most rounds a match can be set to
+const MAX_BEST_OF: u32 = 15;
+
+/// the parts of the app saved with an unfinished game
+#[derive(Serialize, Deserialize)]
+pub struct SavedApp {
+    state: AppState,
+    word_band: WordBand,
+    ai_guesser: bool,
+    /// the moves made so far, older saves have none
+    #[serde(default)]
+    recording: Option<Replay>,
+    /// seconds the game had been played for
+    #[serde(default)]
+    game_time: f64,
+    /// whether the computer made any of the guesses
+    #[serde(default)]
+    ai_assisted: bool,
+    /// the match the game is a round of
+    #[serde(default)]
+    hot_seat: Option<Match>,
+}
+
+/// the other instance of the game when playing over the network
+pub enum Peer {
+    /// hosting and waiting for someone to join
+    Waiting(Listener),
+    /// this instance sets the words and the peer guesses them
+    Guesser(Connection),
+    /// the peer sets the words and this instance guesses them
+    Setter(Connection),
+}
 
 pub struct App<'a> {
     /// OpenGL drawing backend.
     gl: GlGraphics,
     /// Time between frames.
     pub previous_frame_instant: Instant,
-    /// Cache for the font.
-    glyph_cache: GlyphCache<'a>,
+    /// Cache for the fonts.
+    glyph_cache: FallbackGlyphCache<'a>,
     /// the game itself
     pub game: Game,
     /// the state of the app
     pub state: AppState,
+    /// the word or phrase the setter is typing
+    pub typed_word: String,
     /// position of the mouse
     pub mouse_position: [f64; 2],
     /// the size of the window
     pub window_size: [f64; 2], // TODO: this is likely redundant due to GlGraphics having a window size in GLGraphics::current_viewport::window_size
+    /// the difficulty levels the setter can choose between
+    pub difficulties: Vec<DifficultyLevel>,
+    /// index of the chosen difficulty level in difficulties
+    pub difficulty_index: usize,
+    /// the languages the setter can choose between
+    pub languages: Vec<Language>,
+    /// index of the chosen language in languages
+    pub language_index: usize,
+    /// whether the instructions are shown on top of the game
+    pub show_instructions: bool,
+    /// whether the statistics are shown on top of the game
+    pub show_stats: bool,
+    /// the results of every finished game, kept between runs
+    pub stats: Stats,
+    /// whether the text of the key that closed the instructions should be ignored
+    skip_text: bool,
+    /// index of the guessable character under the mouse, if it can be clicked
+    pub hovered_char: Option<usize>,
+    /// the reason the last selected word was refused
+    pub selection_error: Option<GameError>,
+    /// the reason the last hint was refused
+    pub hint_error: Option<HintError>,
+    /// the attempt at the whole word being typed, None when guessing letters
+    pub solve_attempt: Option<String>,
+    /// the last attempt at the whole word if it was wrong
+    pub wrong_solve: Option<String>,
+    /// picks the word when playing against the computer
+    pub rng: StdRng,
+    /// how hard the words picked by the computer are
+    pub word_band: WordBand,
+    /// whether the computer makes the guesses
+    pub ai_guesser: bool,
+    /// the match being played between two players on this screen
+    pub hot_seat: Option<Match>,
+    /// index of the player whose name is being typed before a match
+    pub naming_player: Option<usize>,
+    /// the names typed so far for the next match
+    player_names: [String; 2],
+    /// the most rounds of the next match
+    pub best_of: u32,
+    /// the other instance of the game when playing over the network
+    pub peer: Option<Peer>,
+    /// why the last network game ended or could not start
+    pub network_status: Option<String>,
+    /// the unfinished game from when the app was last closed, until it is resumed or replaced
+    pub saved_game: Option<(Game, SavedApp)>,
+    /// seconds since the computer guesser last guessed
+    ai_guess_timer: f64,
+    /// the moves of the game being played, saved as a replay when it ends
+    recording: Option<Replay>,
+    /// seconds the game being played has been going for
+    game_time: f64,
+    /// whether the computer guesser made any guess in the game being played,
+    /// such games are left out of the statistics
+    ai_assisted: bool,
+    /// the replay of the last finished game
+    pub last_replay: Option<Replay>,
+    /// the replay being played back and the game to return to when it is left
+    pub playback: Option<(Playback, Game)>,
     /// the position of the guessable characters
     guessable_char_pos: Vec<[f64; 2]>,
+    /// font size of the guessable characters, smaller when the alphabet needs more rows
+    guessable_char_font_size: u32,
     scale: f64,
 }
 impl Default for App<'_> {
     fn default() -> App<'static> {
-        let game = Game::default();
+        let (difficulties, errors) = config::get_all_difficulties();
+        for e in errors {
+            eprintln!("skipping custom difficulties, {e}");
+        }
+        let game = Game {
+            difficulty: difficulties[0].clone(),
+            ..Game::default()
+        };
+        let stats = match stats::stats_file_path().as_deref().map(Stats::load) {
+            Some(Ok(stats)) => stats,
+            Some(Err(e)) => {
+                eprintln!("starting with empty statistics, {e}");
+                Stats::default()
+            }
+            None => Stats::default(),
+        };
         let mut app = App {
             gl: GlGraphics::new(OpenGL::V3_2),
             previous_frame_instant: Instant::now(),
-            glyph_cache: GlyphCache::from_bytes(
-                include_bytes!("../../assets/courier-prime-code.regular.ttf"),
-                (),
-                TextureSettings::new(),
-            )
-            .unwrap(),
+            glyph_cache: FallbackGlyphCache::new(),
             state: AppState::Selecting,
+            typed_word: String::new(),
             mouse_position: [0.0, 0.0],
             window_size: [WINDOW_DOTS, WINDOW_DOTS],
+            difficulties,
+            difficulty_index: 0,
+            languages: Language::get_all(),
+            language_index: 0,
+            show_instructions: true,
+            show_stats: false,
+            stats,
+            skip_text: false,
+            hovered_char: None,
+            selection_error: None,
+            hint_error: None,
+            solve_attempt: None,
+            wrong_solve: None,
+            rng: StdRng::from_entropy(),
+            word_band: WordBand::Any,
+            ai_guesser: false,
+            hot_seat: None,
+            naming_player: None,
+            player_names: Default::default(),
+            best_of: hotseat::DEFAULT_BEST_OF,
+            peer: None,
+            network_status: None,
+            saved_game: None,
+            ai_guess_timer: 0.0,
+            recording: None,
+            game_time: 0.0,
+            ai_assisted: false,
+            last_replay: None,
+            playback: None,
             guessable_char_pos: vec![[0.0, 0.0]; game.guessable_characters.len()],
+            guessable_char_font_size: FONT_SIZE,
             game,
             scale: 1.0,
         };
@@ -79,11 +230,36 @@ impl App<'_> {
                 .transform
                 .trans(args.window_size[0] / 2.0, args.window_size[1] / 2.0);
             if self.state == AppState::Selecting {
-                //rendering::instructions(self, &center_anchor, FONT_SIZE).unwrap();
+                rendering::selection_error(self, &center_anchor, FONT_SIZE).unwrap();
+                rendering::difficulty(self, &c, FONT_SIZE).unwrap();
+                if self.saved_game.is_some() {
+                    rendering::resume_offer(self, &c, FONT_SIZE).unwrap();
+                }
+            }
+            if self.state != AppState::Guessing && (self.hot_seat.is_some() || self.naming_player.is_some()) {
+                rendering::hot_seat(self, &c, FONT_SIZE).unwrap();
+            }
+            if self.state == AppState::Guessing {
+                rendering::hints(self, &c, FONT_SIZE).unwrap();
+                rendering::solve_attempt(self, &center_anchor, FONT_SIZE).unwrap();
             }
             rendering::in_progress_word(self, &center_anchor, FONT_SIZE).unwrap();
-            rendering::guessable_characters(self, &c, FONT_SIZE).unwrap();
+            rendering::guessable_characters(self, &c).unwrap();
             rendering::hangman(self, &center_anchor, args).unwrap();
+            if self.peer.is_some() || self.network_status.is_some() {
+                rendering::network(self, &c, FONT_SIZE).unwrap();
+            }
+            if self.playback.is_some() {
+                rendering::playback(self, &c, FONT_SIZE).unwrap();
+            }
+            if self.show_stats {
+                rendering::stats(self, &c, FONT_SIZE).unwrap();
+            }
+            if self.show_instructions {
+                rendering::instructions(self, &c, FONT_SIZE).unwrap();
+            } else {
+                rendering::help_hint(self, &c, FONT_SIZE).unwrap();
+            }
             #[cfg(debug_assertions)]
             rendering::fps_counter(self, &c).unwrap();
         self.gl.draw_end();
@@ -94,85 +270,791 @@ impl App<'_> {
         self.window_size = args.window_size;
         self.scale = (self.window_size[0] / WINDOW_DOTS).min(self.window_size[1] / WINDOW_DOTS);
         self.update_guessable_char_positions();
+        self.update_hovered_char();
     }
 
-    pub fn update(&mut self, _args: &UpdateArgs) {}
+    pub fn update(&mut self, args: &UpdateArgs) {
+        self.update_network();
+        let overlay = self.show_instructions || self.show_stats;
+        if let Some((playback, _)) = &mut self.playback {
+            if !overlay {
+                playback.advance(&mut self.game, args.dt);
+                self.state = match self.game.get_game_state() {
+                    Some(end_state) => AppState::GameOver(end_state),
+                    None => AppState::Guessing,
+                };
+            }
+            return;
+        }
+        if self.state == AppState::Guessing && !overlay {
+            self.game_time += args.dt;
+        }
+        if !self.ai_guesser
+            || !self.can_guess()
+            || self.state != AppState::Guessing
+            || self.solve_attempt.is_some()
+            || self.show_instructions
+            || self.show_stats
+        {
+            self.ai_guess_timer = 0.0;
+            return;
+        }
+        self.ai_guess_timer += args.dt;
+        if self.ai_guess_timer < AI_GUESS_DELAY {
+            return;
+        }
+        self.ai_guess_timer = 0.0;
+        let index = solver::next_guess(&self.game)
+            .and_then(|c| self.game.guessable_characters.iter().position(|gc| gc.0 == c));
+        if let Some(index) = index {
+            self.ai_assisted = true;
+            self.guess_char(index);
+            self.update_hovered_char();
+        }
+    }
 
     /// updates the internal position of the mouse
     pub fn update_mouse_cursor(&mut self, pos: [f64; 2]) {
         self.mouse_position = pos;
+        self.update_hovered_char();
     }
 
     pub fn button(&mut self, args: &ButtonArgs) {
+        if args.state != piston::ButtonState::Press {
+            return;
+        }
+        let previous_state = self.state;
+        self.skip_text = false;
+        // any input closes the instructions, F1 toggles them
+        if args.button == Button::Keyboard(Key::F1) || self.show_instructions {
+            self.skip_text = self.show_instructions;
+            self.show_instructions = !self.show_instructions;
+            self.update_hovered_char();
+            return;
+        }
+        // any input closes the statistics, F7 opens them between games
+        if self.show_stats {
+            self.show_stats = false;
+            self.skip_text = true;
+            self.update_hovered_char();
+            return;
+        }
+        if args.button == Button::Keyboard(Key::F7) && self.state != AppState::Guessing {
+            self.show_stats = true;
+            self.update_hovered_char();
+            return;
+        }
+        if self.playback.is_some() {
+            if let Button::Keyboard(key) = args.button {
+                self.playback_key(key);
+            }
+            self.update_hovered_char();
+            return;
+        }
         match args.button {
+            Button::Keyboard(Key::F3) => {
+                self.ai_guesser = !self.ai_guesser;
+            }
             Button::Keyboard(key) => {
-                if args.state != piston::ButtonState::Press {
-                    return;
-                }
                 match self.state {
                     AppState::GameOver(_) => {
                         if key == Key::Space || key == Key::Return {
-                            self.game.in_progress_word = "".to_string();
+                            // a finished match is left for the next one
+                            if self.hot_seat.as_ref().is_some_and(Match::is_over) {
+                                self.hot_seat = None;
+                            }
+                            self.typed_word.clear();
                             self.state = AppState::Selecting;
+                            // the space would otherwise be typed into the next word
+                            self.skip_text = true;
+                        }
+                        else if key == Key::F2 && self.hot_seat.is_none() && self.peer.is_none() {
+                            self.start_random_game();
+                        }
+                        else if key == Key::F8 && self.peer.is_none() {
+                            if let Some(replay) = self.last_replay.clone() {
+                                self.start_playback(replay);
+                            }
                         }
                     },
+                    // the host picks the word when guessing over the network
+                    AppState::Selecting if !self.can_select() => {}
                     AppState::Selecting => {
-                        if key >= Key::A && key <= Key::Z {
-                            let alphabet_index = key as usize - Key::A as usize;
-                            self.game.in_progress_word.push(self.game.guessable_characters[alphabet_index].0);
+                        if self.naming_player.is_some() {
+                            self.naming_key(key);
+                        }
+                        else if key == Key::F5 && self.peer.is_none() {
+                            self.toggle_hot_seat();
+                        }
+                        else if key == Key::F2 && self.hot_seat.is_none() && self.peer.is_none() {
+                            self.start_random_game();
+                        }
+                        else if key == Key::F6 && self.peer.is_none() {
+                            self.resume_saved_game();
+                        }
+                        else if key == Key::Tab {
+                            self.select_language(self.language_index + 1);
+                        }
+                        else if key == Key::Left {
+                            self.select_difficulty(self.difficulty_index + self.difficulties.len() - 1);
+                        }
+                        else if key == Key::Right {
+                            self.select_difficulty(self.difficulty_index + 1);
+                        }
+                        else if key == Key::Up || key == Key::Down {
+                            let bands = WordBand::get_all();
+                            let index = bands.iter().position(|b| *b == self.word_band).unwrap_or(0);
+                            let step = if key == Key::Up { 1 } else { bands.len() - 1 };
+                            self.word_band = bands[(index + step) % bands.len()];
                         }
                         else if key == Key::Backspace {
-                            self.game.in_progress_word.pop();
+                            self.typed_word.pop();
+                            self.selection_error = None;
                         }
                         else if key == Key::Return {
-                            self.game = match Game::from_game(&self.game, self.game.in_progress_word.clone()) {
+                            self.game = match Game::from_game(&self.game, self.typed_word.clone()) {
                                 Ok(g) => g,
-                                Err(_) => return,
+                                Err(e) => {
+                                    self.selection_error = Some(e);
+                                    return;
+                                }
                             };
+                            self.typed_word.clear();
+                            self.saved_game = None;
                             self.state = AppState::Guessing;
+                            self.start_recording();
                         }
                     },
+                    // the guest guesses when setting over the network
+                    AppState::Guessing if !self.can_guess() => {}
                     AppState::Guessing => {
-                        if key >= Key::A && key <= Key::Z {
-                            let alphabet_index = key as usize - Key::A as usize;
-                            self.game
-                                .guess(self.game.guessable_characters[alphabet_index].0);
-                            if let Some(end_state) = self.game.get_game_state() {
-                                self.state = AppState::GameOver(end_state);
-                            }
+                        if self.solve_attempt.is_some() {
+                            self.solve_key(key);
+                        }
+                        else if key == Key::F4 {
+                            self.use_hint();
+                        }
+                        else if key == Key::Return {
+                            self.solve_attempt = Some(String::new());
+                            self.wrong_solve = None;
                         }
                     }
                 }
             }
             Button::Mouse(button) => {
-                if button == piston::MouseButton::Left { // TODO: mouse click the letters
-                     // scale into virtual coords
-                     // let scalar = self.
-                     // let (x, y) = (
-                     //     self.mouse_position[0] / self.gl. * 1000.0,
-                     //     self.mouse_position[1] / args.window_size[1] * 1000.0,
-                     // );
+                if button == piston::MouseButton::Left {
+                    if let Some(index) = self.hovered_char {
+                        match self.state {
+                            AppState::Selecting if self.can_select() => self.select_char(index),
+                            AppState::Guessing if !self.can_guess() => {}
+                            AppState::Guessing if self.solve_attempt.is_some() => {
+                                self.type_solve_char(index);
+                            }
+                            AppState::Guessing => self.guess_char(index),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        if self.state != previous_state {
+            self.sync_peer();
+        }
+        self.update_hovered_char();
+    }
+
+    /// handles typed text, letters are typed or guessed in the alphabet of the language
+    pub fn text(&mut self, text: &str) {
+        if std::mem::take(&mut self.skip_text)
+            || self.show_instructions
+            || self.show_stats
+            || self.playback.is_some()
+        {
+            return;
+        }
+        for c in text.chars() {
+            // names are typed as they are, in any script
+            if self.state == AppState::Selecting && self.naming_player.is_some() {
+                if !c.is_control() {
+                    self.typed_word.push(c);
+                }
+                continue;
+            }
+            let c = self.game.language.to_uppercase_char(c);
+            // accented letters, spaces and punctuation are typed as they are into a word or an attempt
+            let typeable = self.game.language.is_letter(c) || is_revealed_character(c);
+            match self.state {
+                AppState::Selecting if typeable && self.can_select() => {
+                    self.typed_word.push(c);
+                    self.selection_error = None;
+                }
+                AppState::Guessing if self.solve_attempt.is_some() => {
+                    if let Some(attempt) = self.solve_attempt.as_mut().filter(|_| typeable) {
+                        attempt.push(c);
+                    }
                 }
+                AppState::Guessing if self.can_guess() => {
+                    // an accented letter is guessed as the letter it is folded into
+                    let c = self.game.guess_letter(c);
+                    if let Some(index) = self.game.guessable_characters.iter().position(|gc| gc.0 == c) {
+                        self.guess_char(index);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.update_hovered_char();
+    }
+
+    /// changes the language of the next game, wrapping around the list of languages
+    pub fn select_language(&mut self, index: usize) {
+        self.language_index = index % self.languages.len();
+        let language = self.languages[self.language_index].clone();
+        self.game.language = language;
+        self.game.set_fold_diacritics(self.game.fold_diacritics);
+        self.typed_word.clear();
+        self.selection_error = None;
+        self.guessable_char_pos = vec![[0.0, 0.0]; self.game.guessable_characters.len()];
+        self.update_guessable_char_positions();
+    }
+
+    /// changes the difficulty of the next game, wrapping around the list of levels
+    fn select_difficulty(&mut self, index: usize) {
+        self.difficulty_index = index % self.difficulties.len();
+        self.game.difficulty = self.difficulties[self.difficulty_index].clone();
+    }
+
+    /// skips selecting and starts guessing a random word from the dictionary
+    fn start_random_game(&mut self) {
+        if let Some(game) = picker::random_game_in_band(
+            &mut self.rng,
+            &self.languages[self.language_index],
+            &self.difficulties[self.difficulty_index],
+            self.word_band.range(&self.languages[self.language_index]),
+        ) {
+            let fold_diacritics = self.game.fold_diacritics;
+            self.game = Game {
+                max_hints: self.game.max_hints,
+                solve_penalty: self.game.solve_penalty,
+                ..game
+            };
+            self.game.set_fold_diacritics(fold_diacritics);
+            self.typed_word.clear();
+            self.selection_error = None;
+            self.saved_game = None;
+            self.state = AppState::Guessing;
+            self.start_recording();
+        }
+    }
+
+    /// starts hosting a game over the network, the guest guesses the words set here
+    pub fn host(&mut self, address: &str, name: &str) {
+        match Listener::bind(address, name) {
+            Ok(listener) => {
+                self.peer = Some(Peer::Waiting(listener));
+                self.network_status = None;
+            }
+            Err(e) => self.network_status = Some(format!("can not host on {address}, {e}")),
+        }
+    }
+
+    /// joins a game hosted over the network and guesses the words set there
+    pub fn join(&mut self, address: &str, name: &str) {
+        match Connection::join(address, name) {
+            Ok(connection) => {
+                self.peer = Some(Peer::Setter(connection));
+                self.network_status = None;
+                self.state = AppState::Selecting;
+                self.typed_word.clear();
+            }
+            Err(e) => self.network_status = Some(format!("can not join {address}, {e}")),
+        }
+    }
+
+    /// tells the other instance that this one is closing
+    pub fn leave_network(&mut self) {
+        self.send_to_peer(&Message::Bye);
+    }
+
+    /// whether the word can be picked here, not when the peer sets the words
+    fn can_select(&self) -> bool {
+        !matches!(self.peer, Some(Peer::Setter(_)))
+    }
+
+    /// whether the word can be guessed here, not when the peer guesses the words
+    fn can_guess(&self) -> bool {
+        !matches!(self.peer, Some(Peer::Guesser(_)))
+    }
+
+    /// accepts a guest and handles the messages that have arrived from the other instance
+    fn update_network(&mut self) {
+        if let Some(Peer::Waiting(listener)) = &mut self.peer {
+            match listener.poll() {
+                Ok(Some(connection)) => {
+                    self.peer = Some(Peer::Guesser(connection));
+                    self.sync_peer();
+                }
+                Ok(None) => {}
+                // only that guest is turned away, others can still join
+                Err(e) => eprintln!("a player could not join, {e}"),
+            }
+            return;
+        }
+        let mut messages = Vec::new();
+        let mut error = None;
+        if let Some(Peer::Guesser(connection) | Peer::Setter(connection)) = &mut self.peer {
+            loop {
+                match connection.poll() {
+                    Ok(Some(message)) => messages.push(message),
+                    Ok(None) => break,
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                }
+            }
+        }
+        if messages.is_empty() && error.is_none() {
+            return;
+        }
+        for message in messages {
+            // anything after a goodbye is ignored
+            if self.peer.is_none() {
+                break;
+            }
+            self.handle_message(message);
+        }
+        if let Some(e) = error {
+            self.disconnect(e.to_string());
+        }
+        self.update_hovered_char();
+    }
+
+    /// applies the moves of the guest on the host, and mirrors the game of the host on the guest
+    fn handle_message(&mut self, message: Message) {
+        let hosting = matches!(self.peer, Some(Peer::Guesser(_)));
+        let guessing = self.state == AppState::Guessing;
+        match message {
+            Message::Guess { letter } if hosting && guessing => {
+                let letter = self.game.guess_letter(self.game.language.to_uppercase_char(letter));
+                if let Some(index) = self.game.guessable_characters.iter().position(|gc| gc.0 == letter) {
+                    self.guess_char(index);
+                }
+            }
+            Message::Hint if hosting && guessing => self.use_hint(),
+            Message::Solve { attempt } if hosting && guessing => self.solve(attempt),
+            Message::Setting if !hosting => {
+                self.ai_assisted = false;
+                self.state = AppState::Selecting;
+                self.typed_word.clear();
+            }
+            Message::State { view } if !hosting => self.mirror_game(view.to_game()),
+            Message::GameOver { game } if !hosting => self.mirror_game(*game),
+            Message::Bye => {
+                let name = match &self.peer {
+                    Some(Peer::Guesser(connection) | Peer::Setter(connection)) => connection.peer_name().to_string(),
+                    _ => String::new(),
+                };
+                self.disconnect(format!("{name} left"));
+                return;
             }
             _ => {}
         }
+        // every move is answered so the guest knows where the game is
+        if hosting {
+            self.sync_peer();
+        }
+    }
+
+    /// shows the game of the host on the guest, the word is only known once it is over
+    fn mirror_game(&mut self, game: Game) {
+        let was_over = matches!(self.state, AppState::GameOver(_));
+        self.game = game;
+        self.guessable_char_pos = vec![[0.0, 0.0]; self.game.guessable_characters.len()];
+        self.update_guessable_char_positions();
+        match self.game.get_game_state() {
+            Some(_) if !was_over => self.check_game_over(),
+            Some(end_state) => self.state = AppState::GameOver(end_state),
+            None => self.state = AppState::Guessing,
+        }
+    }
+
+    /// sends the game to the guest, or that a new word is being picked
+    fn sync_peer(&mut self) {
+        if !matches!(self.peer, Some(Peer::Guesser(_))) {
+            return;
+        }
+        let message = match self.state {
+            AppState::Selecting => Message::Setting,
+            _ => network::game_message(&self.game),
+        };
+        self.send_to_peer(&message);
+    }
+
+    /// sends a move to the host instead of making it here when guessing over the network
+    fn send_move(&mut self, message: Message) -> bool {
+        if !matches!(self.peer, Some(Peer::Setter(_))) {
+            return false;
+        }
+        self.send_to_peer(&message);
+        true
+    }
+
+    /// sends a message to the other instance, the connection is dropped if it fails
+    fn send_to_peer(&mut self, message: &Message) {
+        let result = match &mut self.peer {
+            Some(Peer::Guesser(connection) | Peer::Setter(connection)) => connection.send(message),
+            _ => return,
+        };
+        if let Err(e) = result {
+            self.disconnect(format!("lost the connection, {e}"));
+        }
+    }
+
+    /// stops playing over the network, an unfinished round is abandoned
+    fn disconnect(&mut self, status: String) {
+        self.peer = None;
+        self.network_status = Some(status);
+        if self.state == AppState::Guessing {
+            self.state = AppState::Selecting;
+            self.recording = None;
+            self.solve_attempt = None;
+            self.typed_word.clear();
+        }
+    }
+
+    /// starts typing the names for a new match, or leaves the match being played
+    fn toggle_hot_seat(&mut self) {
+        if self.hot_seat.take().is_none() {
+            self.naming_player = Some(0);
+            self.player_names = Default::default();
+        }
+        self.typed_word.clear();
+        self.selection_error = None;
+    }
+
+    /// handles a key while the names of the players are typed
+    fn naming_key(&mut self, key: Key) {
+        let player = match self.naming_player {
+            Some(player) => player,
+            None => return,
+        };
+        if key == Key::F5 {
+            self.naming_player = None;
+            self.typed_word.clear();
+        }
+        else if key == Key::Backspace {
+            self.typed_word.pop();
+        }
+        else if key == Key::Up {
+            self.best_of = (self.best_of + 2).min(MAX_BEST_OF);
+        }
+        else if key == Key::Down {
+            self.best_of = self.best_of.saturating_sub(2).max(1);
+        }
+        else if key == Key::Return {
+            self.player_names[player] = std::mem::take(&mut self.typed_word);
+            if player == 0 {
+                self.naming_player = Some(1);
+            } else {
+                self.naming_player = None;
+                self.hot_seat = Some(Match::new(std::mem::take(&mut self.player_names), self.best_of));
+            }
+        }
+    }
+
+    /// starts recording the moves of the game that was just set up
+    fn start_recording(&mut self) {
+        self.recording = Some(Replay::new(&self.game));
+        self.game_time = 0.0;
+        self.ai_assisted = false;
+    }
+
+    /// adds a move that changed the game to the recording
+    fn record(&mut self, action: Move) {
+        if let Some(recording) = &mut self.recording {
+            recording.record(self.game_time, action);
+        }
+    }
+
+    /// plays a replay back, the game being selected is restored when it is left
+    pub fn start_playback(&mut self, replay: Replay) {
+        let (playback, game) = Playback::new(replay);
+        let previous = std::mem::replace(&mut self.game, game);
+        self.playback = Some((playback, previous));
+        self.state = AppState::Guessing;
+        self.solve_attempt = None;
+        self.wrong_solve = None;
+        self.hint_error = None;
+        self.guessable_char_pos = vec![[0.0, 0.0]; self.game.guessable_characters.len()];
+        self.update_guessable_char_positions();
+    }
+
+    /// handles a key while a replay is played back
+    fn playback_key(&mut self, key: Key) {
+        let (playback, _) = match &mut self.playback {
+            Some(playback) => playback,
+            None => return,
+        };
+        match key {
+            Key::Space => playback.paused = !playback.paused,
+            Key::Right => {
+                playback.paused = true;
+                playback.step(&mut self.game);
+            }
+            Key::Up => playback.speed = (playback.speed * 2.0).min(PLAYBACK_SPEEDS[1]),
+            Key::Down => playback.speed = (playback.speed / 2.0).max(PLAYBACK_SPEEDS[0]),
+            Key::Return | Key::Backspace => {
+                if let Some((_, previous)) = self.playback.take() {
+                    self.game = previous;
+                }
+                self.typed_word.clear();
+                self.state = AppState::Selecting;
+                self.guessable_char_pos = vec![[0.0, 0.0]; self.game.guessable_characters.len()];
+                self.update_guessable_char_positions();
+            }
+            _ => {}
+        }
+    }
+
+    /// offers to resume the game that was unfinished when the app was last closed
+    pub fn load_save(&mut self) {
+        let path = match save::save_file_path() {
+            Some(path) if path.is_file() => path,
+            _ => return,
+        };
+        match save::load_game::<SavedApp>(&path) {
+            Ok(saved) => self.saved_game = Some(saved),
+            Err(e) => eprintln!("skipping the saved game, {e}"),
+        }
+    }
+
+    /// saves an unfinished game so it can be resumed on the next launch,
+    /// otherwise removes the old save unless it is still being offered
+    pub fn autosave(&self) {
+        let path = match save::save_file_path() {
+            Some(path) => path,
+            None => return,
+        };
+        let result = if self.state == AppState::Guessing && self.playback.is_none() && self.peer.is_none() {
+            let saved = SavedApp {
+                state: AppState::Guessing,
+                word_band: self.word_band,
+                ai_guesser: self.ai_guesser,
+                recording: self.recording.clone(),
+                game_time: self.game_time,
+                ai_assisted: self.ai_assisted,
+                hot_seat: self.hot_seat.clone(),
+            };
+            save::save_game(&path, &self.game, &saved)
+        } else if self.saved_game.is_none() {
+            save::delete_save(&path)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            eprintln!("could not save the game, {e}");
+        }
+    }
+
+    /// continues the saved game with the language and difficulty it was played with
+    fn resume_saved_game(&mut self) {
+        let (game, saved) = match self.saved_game.take() {
+            Some(saved) => saved,
+            None => return,
+        };
+        if let Some(index) = self.languages.iter().position(|l| l.code() == game.language.code()) {
+            self.language_index = index;
+        }
+        // a custom difficulty that has since been removed from the file is added back
+        self.difficulty_index = match self.difficulties.iter().position(|d| d.name() == game.difficulty.name()) {
+            Some(index) => index,
+            None => {
+                self.difficulties.push(game.difficulty.clone());
+                self.difficulties.len() - 1
+            }
+        };
+        self.game = game;
+        self.state = saved.state;
+        self.word_band = saved.word_band;
+        self.ai_guesser = saved.ai_guesser;
+        self.recording = saved.recording;
+        self.game_time = saved.game_time;
+        self.ai_assisted = saved.ai_assisted;
+        self.hot_seat = saved.hot_seat;
+        self.typed_word.clear();
+        self.selection_error = None;
+        self.guessable_char_pos = vec![[0.0, 0.0]; self.game.guessable_characters.len()];
+        self.update_guessable_char_positions();
+    }
+
+    /// reveals a random hidden letter at the cost of one drawing element
+    fn use_hint(&mut self) {
+        if self.send_move(Message::Hint) {
+            return;
+        }
+        if let Some(letter) = self.game.random_hidden_letter(&mut self.rng) {
+            self.hint_error = self.game.hint(letter).err();
+            if self.hint_error.is_none() {
+                self.record(Move::Hint(letter));
+            }
+        }
+        self.check_game_over();
+    }
+
+    /// handles a key while the guesser is typing an attempt at the whole word
+    fn solve_key(&mut self, key: Key) {
+        if key == Key::Backspace {
+            // erasing past the first letter leaves solve mode
+            if let Some(attempt) = &mut self.solve_attempt {
+                if attempt.pop().is_none() {
+                    self.solve_attempt = None;
+                }
+            }
+        }
+        else if key == Key::Return {
+            let attempt = self.solve_attempt.take().unwrap_or_default();
+            self.solve(attempt);
+        }
+    }
+
+    /// guesses the whole word and ends the game if it is decided
+    fn solve(&mut self, attempt: String) {
+        if self.send_move(Message::Solve { attempt: attempt.clone() }) {
+            return;
+        }
+        let outcome = self.game.solve(&attempt);
+        if matches!(outcome, SolveOutcome::Correct | SolveOutcome::Wrong) {
+            self.record(Move::Solve(attempt.clone()));
+        }
+        if outcome == SolveOutcome::Wrong {
+            self.wrong_solve = Some(attempt);
+        }
+        self.check_game_over();
+    }
+
+    /// types the guessable character at index into the attempt at the whole word
+    fn type_solve_char(&mut self, index: usize) {
+        let c = self.game.guessable_characters[index].0;
+        if let Some(attempt) = &mut self.solve_attempt {
+            attempt.push(c);
+        }
+    }
+
+    /// types the guessable character at index into the word being selected
+    fn select_char(&mut self, index: usize) {
+        self.typed_word.push(self.game.guessable_characters[index].0);
+        self.selection_error = None;
+    }
+
+    /// guesses the guessable character at index and ends the game if it is decided
+    fn guess_char(&mut self, index: usize) {
+        let letter = self.game.guessable_characters[index].0;
+        if self.send_move(Message::Guess { letter }) {
+            return;
+        }
+        if matches!(self.game.guess(letter), GuessOutcome::Hit(_) | GuessOutcome::Miss) {
+            self.record(Move::Guess(letter));
+        }
+        self.hint_error = None;
+        self.wrong_solve = None;
+        self.check_game_over();
+    }
+
+    /// ends the game if it is decided and adds it to the statistics
+    fn check_game_over(&mut self) {
+        let end_state = match self.game.get_game_state() {
+            Some(end_state) => end_state,
+            None => return,
+        };
+        self.state = AppState::GameOver(end_state);
+        if let Some(hot_seat) = &mut self.hot_seat {
+            hot_seat.record(&self.game);
+        }
+        // the statistics are of the player guessing on this screen, without help from the computer,
+        // the rounds of a match are left out as they are guessed by two players in turn
+        if self.can_guess() && !self.ai_assisted && self.hot_seat.is_none() {
+            self.stats.record(&self.game);
+            if let Some(path) = stats::stats_file_path() {
+                if let Err(e) = self.stats.save(&path) {
+                    eprintln!("could not save the statistics, {e}");
+                }
+            }
+        }
+        if let Some(replay) = self.recording.take() {
+            if let Some(dir) = replay::replay_dir() {
+                if let Err(e) = replay.save_new(&dir) {
+                    eprintln!("could not save the replay, {e}");
+                }
+            }
+            self.last_replay = Some(replay);
+        }
+    }
+
+    /// font size of the guessable characters
+    fn guessable_char_font_size(&self) -> u32 {
+        self.guessable_char_font_size
+    }
+
+    /// finds the guessable character that can be clicked under the mouse
+    fn update_hovered_char(&mut self) {
+        self.hovered_char = None;
+        let font_size = self.guessable_char_font_size();
+        for (i, gc) in self.game.guessable_characters.iter().enumerate() {
+            let clickable = match self.state {
+                AppState::Selecting => self.can_select(),
+                AppState::Guessing if !self.can_guess() => false,
+                AppState::Guessing if self.solve_attempt.is_some() => true,
+                AppState::Guessing => !gc.1,
+                AppState::GameOver(_) => false,
+            };
+            if !clickable || self.show_instructions || self.show_stats || self.playback.is_some() {
+                continue;
+            }
+            // text is drawn upwards from the baseline at its position
+            let width = match self.glyph_cache.width(font_size, &gc.0.to_string()) {
+                Ok(width) => width,
+                Err(_) => continue,
+            };
+            let [x, y] = self.guessable_char_pos[i];
+            if self.mouse_position[0] >= x
+                && self.mouse_position[0] <= x + width
+                && self.mouse_position[1] >= y - font_size as f64
+                && self.mouse_position[1] <= y
+            {
+                self.hovered_char = Some(i);
+                return;
+            }
+        }
     }
 
     fn update_guessable_char_positions(&mut self) {
-        // calculate total number of rows needed to display all the characters
-        let scaled_font_size: u32 = (FONT_SIZE as f64 * self.scale) as u32;
-        let row_spacing = scaled_font_size as f64 * 2.0;
-        let col_spacing = scaled_font_size as f64 * 1.3;
         let max_row_size_scalar: f64 = 0.8;
-        let chars_per_row =
-            (self.window_size[0] * max_row_size_scalar / col_spacing).floor() as u32;
-        let rows = (self.guessable_char_pos.len() as f64 / chars_per_row as f64).ceil() as u32;
+        let top = self.window_size[1] / (2.0 * 0.8);
+        let char_count = self.guessable_char_pos.len() as u32;
+        // shrink the characters until every row fits between the hangman and the bottom edge
+        let mut scaled_font_size: u32 = (FONT_SIZE as f64 * self.scale) as u32;
+        let (row_spacing, col_spacing, chars_per_row, rows) = loop {
+            let row_spacing = scaled_font_size as f64 * 2.0;
+            let col_spacing = scaled_font_size as f64 * 1.3;
+            // calculate total number of rows needed to display all the characters
+            let chars_per_row =
+                ((self.window_size[0] * max_row_size_scalar / col_spacing).floor() as u32).max(1);
+            let rows = (char_count as f64 / chars_per_row as f64).ceil() as u32;
+            let bottom = top + rows.saturating_sub(1) as f64 * row_spacing;
+            if bottom <= self.window_size[1] - row_spacing / 4.0 || scaled_font_size <= 10 {
+                break (row_spacing, col_spacing, chars_per_row, rows);
+            }
+            scaled_font_size -= 1;
+        };
+        self.guessable_char_font_size = (scaled_font_size as f64 * 0.8) as u32;
         // --- per char ---
         for row in 0..rows {
             // number of chars in this row
             let cols = min(
-                chars_per_row,                                              // max
-                self.guessable_char_pos.len() as u32 - row * chars_per_row, // leftovers
+                chars_per_row,                    // max
+                char_count - row * chars_per_row, // leftovers
             );
             for col in 0..cols {
                 // positioning
@@ -180,7 +1062,7 @@ impl App<'_> {
                     (col as f64 * col_spacing)
                         + ((chars_per_row - cols) as f64 * col_spacing / 2.0)
                         + (self.window_size[0] * (1.0 - max_row_size_scalar) / 2.0),
-                    self.window_size[1] / (2.0 * 0.8) + (row as f64 * row_spacing),
+                    top + (row as f64 * row_spacing),
                 ];
             }
         }
@@ -5,3 +5,4 @@ pub const LOSS_RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
 pub const VICTORY_GREEN_DARK: [f32; 4] = [0.0, 0.4, 0.0, 1.0];
 pub const LOSS_RED_DARK: [f32; 4] = [0.4, 0.0, 0.0, 1.0];
 pub const GREY: [f32; 4] = [0.4, 0.4, 0.4, 1.0];
+pub const HIGHLIGHT_YELLOW: [f32; 4] = [1.0, 0.9, 0.3, 1.0];
@@ -0,0 +1,41 @@
+use graphics::character::{Character, CharacterCache};
+use graphics::types::FontSize;
+use opengl_graphics::{GlyphCache, Texture, TextureSettings};
+
+/// glyph caches tried in order, each character is drawn with the first font that has it
+pub struct FallbackGlyphCache<'a> {
+    caches: Vec<GlyphCache<'a>>,
+}
+impl FallbackGlyphCache<'static> {
+    /// courier prime for latin letters, then dejavu sans mono for greek, cyrillic and the rest
+    pub fn new() -> FallbackGlyphCache<'static> {
+        let fonts: [&'static [u8]; 2] = [
+            include_bytes!("../../../assets/courier-prime-code.regular.ttf"),
+            include_bytes!("../../../assets/dejavu-sans-mono.ttf"),
+        ];
+        FallbackGlyphCache {
+            caches: fonts
+                .into_iter()
+                .map(|font| GlyphCache::from_bytes(font, (), TextureSettings::new()).unwrap())
+                .collect(),
+        }
+    }
+}
+impl<'a> CharacterCache for FallbackGlyphCache<'a> {
+    type Texture = Texture;
+    type Error = <GlyphCache<'a> as CharacterCache>::Error;
+
+    fn character<'b>(
+        &'b mut self,
+        font_size: FontSize,
+        ch: char,
+    ) -> Result<Character<'b, Texture>, Self::Error> {
+        // glyph 0 is the missing glyph box, the first font draws it if no font has the character
+        let index = self
+            .caches
+            .iter()
+            .position(|cache| cache.font.glyph(ch).id().0 != 0)
+            .unwrap_or(0);
+        self.caches[index].character(font_size, ch)
+    }
+}
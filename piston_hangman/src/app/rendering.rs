@@ -5,6 +5,8 @@ use graphics::*;
 use super::colors::*;
 use super::App;
 use super::AppState;
+use super::Peer;
+use super::WINDOW_DOTS;
 
 /// draws a fps counter in the top left corner
 pub fn fps_counter(app: &mut App, c: &Context) -> Result<(), String> {
@@ -51,10 +53,10 @@ pub fn hangman(
             .trans(97.0 * app.scale, -150.0 * app.scale)
             .rot_deg(20.0 * rotation_factor)
             .scale(app.scale, app.scale);
-        let draw_count = if app.state == AppState::Selecting || app.game.guess_count >= app.game.difficulty.0.len() as i32 {
+        let draw_count = if app.state == AppState::Selecting {
             app.game.difficulty.0.len()
         } else {
-            app.game.guess_count as usize
+            usize::try_from(app.game.guess_count).unwrap_or(0).min(app.game.difficulty.0.len())
         };
         for i in 0..draw_count {
             let elem = &app.game.difficulty.0[i];
@@ -146,9 +148,8 @@ pub fn hangman(
 pub fn guessable_characters(
     app: &mut App,
     c: &Context,
-    font_size: u32,
 ) -> Result<(), String> {
-    let scaled_font_size: u32 = (font_size as f64 * app.scale) as u32;
+    let font_size = app.guessable_char_font_size();
     for i in 0..app.game.guessable_characters.len() {
         let color = match app.state {
             AppState::GameOver(state) => {
@@ -166,6 +167,7 @@ pub fn guessable_characters(
                     }
                 }
             }
+            _ if app.hovered_char == Some(i) => HIGHLIGHT_YELLOW,
             AppState::Selecting => WHITE,
             AppState::Guessing => {
                 if app.game.guessable_characters[i].1 {
@@ -177,7 +179,7 @@ pub fn guessable_characters(
         };
         text(
             color,
-            (scaled_font_size as f64 * 0.8) as u32,
+            font_size,
             app.game.guessable_characters[i].0.to_string().as_str(),
             &mut app.glyph_cache,
             c.transform
@@ -188,13 +190,29 @@ pub fn guessable_characters(
     Ok(())
 }
 
-/// draws the in progress word
+/// splits a phrase into lines of at most max_chars characters at its spaces,
+/// a word longer than a line gets a line of its own
+fn wrap_words(phrase: &str, max_chars: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in phrase.split(' ') {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= max_chars => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+/// draws the in progress word, a phrase that is too wide is wrapped over several lines
 pub fn in_progress_word(
     app: &mut App,
     transform: &types::Matrix2d,
     font_size: u32,
 ) -> Result<(), String> {
-    let scaled_font_size: u32 = (font_size as f64 * app.scale) as u32;
+    let mut scaled_font_size: u32 = (font_size as f64 * app.scale) as u32;
     let text_color = if let AppState::GameOver(state) = app.state {
         if state {
             VICTORY_GREEN
@@ -204,22 +222,434 @@ pub fn in_progress_word(
     } else {
         WHITE
     };
+    let word = if app.state == AppState::Selecting {
+        app.typed_word.clone()
+    } else {
+        app.game.in_progress_word()
+    };
+    // characters are about 0.8 font sizes wide, shrink the text if more than two lines are needed
+    let lines = loop {
+        let max_chars = (app.window_size[0] * 0.9 / (scaled_font_size as f64 * 0.8)) as usize;
+        let lines = wrap_words(&word, max_chars.max(1));
+        if lines.len() <= 2 || scaled_font_size <= 16 {
+            break lines;
+        }
+        scaled_font_size -= 2;
+    };
+    let line_spacing = scaled_font_size as f64 * 1.2;
+    let top = -(lines.len() as f64 - 1.0) * line_spacing / 2.0;
+    for (i, line) in lines.iter().enumerate() {
+        text(
+            text_color,
+            scaled_font_size,
+            line,
+            &mut app.glyph_cache,
+            transform.trans(
+                (scaled_font_size as usize * line.chars().count()) as f64 / -2.5,
+                top + i as f64 * line_spacing,
+            ),
+            &mut app.gl,
+        )?;
+    }
+    Ok(())
+}
+
+/// draws the chosen difficulty level, its number of lives and how hard random words are
+/// at the top of the screen
+pub fn difficulty(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.5) as u32;
+    let label = match app.game.difficulty.max_word_length() {
+        Some(max) => format!(
+            "< {} - {} lives, max {} letters >",
+            app.game.difficulty.name(),
+            app.game.difficulty.lives(),
+            max
+        ),
+        None => format!(
+            "< {} - {} lives >",
+            app.game.difficulty.name(),
+            app.game.difficulty.lives()
+        ),
+    };
+    text(
+        WHITE,
+        scaled_font_size,
+        &label,
+        &mut app.glyph_cache,
+        c.transform.trans(
+            app.window_size[0] / 2.0
+                + (scaled_font_size as usize * label.chars().count()) as f64 / -2.5,
+            scaled_font_size as f64 * 2.0,
+        ),
+        &mut app.gl,
+    )?;
+    let band_label = format!(
+        "{}, random words: {}",
+        app.game.language.name(),
+        app.word_band.name()
+    );
     text(
-        text_color,
+        GREY,
         scaled_font_size,
-        &app.game.in_progress_word,
+        &band_label,
+        &mut app.glyph_cache,
+        c.transform.trans(
+            app.window_size[0] / 2.0
+                + (scaled_font_size as usize * band_label.chars().count()) as f64 / -2.5,
+            scaled_font_size as f64 * 3.5,
+        ),
+        &mut app.gl,
+    )
+}
+
+/// draws the offer to resume the game that was unfinished when the app was last closed
+pub fn resume_offer(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.5) as u32;
+    let label = "F6: resume the saved game";
+    text(
+        HIGHLIGHT_YELLOW,
+        scaled_font_size,
+        label,
+        &mut app.glyph_cache,
+        c.transform.trans(
+            app.window_size[0] / 2.0
+                + (scaled_font_size as usize * label.chars().count()) as f64 / -2.5,
+            scaled_font_size as f64 * 5.0,
+        ),
+        &mut app.gl,
+    )
+}
+
+/// draws the names being typed for a match, or the score and roles of the match being played
+pub fn hot_seat(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.5) as u32;
+    let lines = match (&app.hot_seat, app.naming_player) {
+        (_, Some(player)) => vec![
+            format!("name of player {}, Return to confirm", player + 1),
+            format!("best of {} rounds (Up/Down)", app.best_of),
+        ],
+        (Some(hot_seat), None) => {
+            let next = if !hot_seat.is_over() {
+                format!(
+                    "round {}: {} sets the word for {}",
+                    hot_seat.round_number(),
+                    hot_seat.players[hot_seat.setter()],
+                    hot_seat.players[hot_seat.guesser()]
+                )
+            } else if let Some(winner) = hot_seat.winner() {
+                format!("{} wins the match!", hot_seat.players[winner])
+            } else {
+                "the match is a draw".to_string()
+            };
+            vec![format!("{}, best of {}", hot_seat.scoreboard(), hot_seat.best_of), next]
+        }
+        (None, None) => return Ok(()),
+    };
+    for (i, line) in lines.iter().enumerate() {
+        text(
+            HIGHLIGHT_YELLOW,
+            scaled_font_size,
+            line,
+            &mut app.glyph_cache,
+            c.transform.trans(
+                app.window_size[0] / 2.0
+                    + (scaled_font_size as usize * line.chars().count()) as f64 / -2.5,
+                scaled_font_size as f64 * (6.5 + 1.5 * i as f64),
+            ),
+            &mut app.gl,
+        )?;
+    }
+    Ok(())
+}
+
+/// draws the number of hints left, or why the last one was refused, at the top of the screen
+pub fn hints(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.5) as u32;
+    let (label, color) = match app.hint_error {
+        Some(e) => (format!("no hint, {e}"), LOSS_RED),
+        None => (
+            format!(
+                "hints left: {} (F4)",
+                app.game.max_hints.saturating_sub(app.game.hints_used)
+            ),
+            GREY,
+        ),
+    };
+    text(
+        color,
+        scaled_font_size,
+        &label,
+        &mut app.glyph_cache,
+        c.transform.trans(
+            app.window_size[0] / 2.0
+                + (scaled_font_size as usize * label.chars().count()) as f64 / -2.5,
+            scaled_font_size as f64 * 2.0,
+        ),
+        &mut app.gl,
+    )
+}
+
+/// draws the attempt at the whole word being typed, or the last wrong attempt, below the word
+pub fn solve_attempt(
+    app: &mut App,
+    transform: &types::Matrix2d,
+    font_size: u32,
+) -> Result<(), String> {
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.5) as u32;
+    let (message, color) = match (&app.solve_attempt, &app.wrong_solve) {
+        (Some(attempt), _) => (
+            format!(
+                "solve: {attempt}_ (Return, costs {})",
+                app.game.solve_penalty
+            ),
+            HIGHLIGHT_YELLOW,
+        ),
+        (None, Some(attempt)) => (format!("{attempt} is not the word"), LOSS_RED),
+        (None, None) => return Ok(()),
+    };
+    text(
+        color,
+        scaled_font_size,
+        &message,
         &mut app.glyph_cache,
         transform.trans(
-            (scaled_font_size as usize * &app.game.in_progress_word.chars().count())
-                as f64
-                / -2.5,
-            0.0,
+            (scaled_font_size as usize * message.chars().count()) as f64 / -2.5,
+            scaled_font_size as f64 * 2.0,
         ),
         &mut app.gl,
     )
 }
 
-/// draws the instructions for how to select a word and difficulty
-pub fn instructions() {
-    todo!()
-}
\ No newline at end of file
+/// draws the reason the selected word was refused below the word
+pub fn selection_error(
+    app: &mut App,
+    transform: &types::Matrix2d,
+    font_size: u32,
+) -> Result<(), String> {
+    let message = match &app.selection_error {
+        Some(e) => e.to_string(),
+        None => return Ok(()),
+    };
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.5) as u32;
+    text(
+        LOSS_RED,
+        scaled_font_size,
+        &message,
+        &mut app.glyph_cache,
+        transform.trans(
+            (scaled_font_size as usize * message.chars().count()) as f64 / -2.5,
+            scaled_font_size as f64 * 2.0,
+        ),
+        &mut app.gl,
+    )
+}
+
+/// draws how far the replay has been played back and its controls below the hints
+pub fn playback(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.4) as u32;
+    let (playback, _) = match &app.playback {
+        Some(playback) => playback,
+        None => return Ok(()),
+    };
+    let status = if playback.is_finished() {
+        "finished"
+    } else if playback.paused {
+        "paused"
+    } else {
+        "playing"
+    };
+    let lines = [
+        format!(
+            "replay: move {}/{}, {}, speed x{}",
+            playback.moves_played(),
+            playback.replay().moves.len(),
+            status,
+            playback.speed
+        ),
+        "Space: pause  Right: step  Up/Down: speed  Return: leave".to_string(),
+    ];
+    for (i, line) in lines.iter().enumerate() {
+        text(
+            HIGHLIGHT_YELLOW,
+            scaled_font_size,
+            line,
+            &mut app.glyph_cache,
+            c.transform.trans(
+                app.window_size[0] / 2.0
+                    + (scaled_font_size as usize * line.chars().count()) as f64 / -2.5,
+                scaled_font_size as f64 * (4.5 + 1.5 * i as f64),
+            ),
+            &mut app.gl,
+        )?;
+    }
+    Ok(())
+}
+
+/// lines of the instructions, padded so the key column lines up in a monospace font
+const INSTRUCTIONS: [&str; 28] = [
+    "HOW TO PLAY",
+    "",
+    "The setter picks a difficulty, types a",
+    "secret word and presses Return, then the",
+    "guesser picks letters with the keyboard",
+    "or the mouse. Every miss draws part of",
+    "the hangman, find the word before it is",
+    "complete.",
+    "",
+    "Letters    type or guess a letter",
+    "Click      type or guess a letter",
+    "Left/Right change the difficulty",
+    "Tab        change the language",
+    "Backspace  erase the last letter",
+    "Return     setting: start guessing the word",
+    "           guessing: guess the whole word",
+    "Space      new round after game over",
+    "F2         guess a random word alone",
+    "Up/Down    how hard random words are",
+    "F3         let the computer guess",
+    "F4         reveal a letter, costs a life",
+    "F5         start or leave a two player match",
+    "F6         resume the saved game",
+    "F7         show the statistics",
+    "F8         replay the last game",
+    "F1         show or hide this help",
+    "Esc        quit",
+    "",
+];
+
+/// draws the wins, losses, streaks and average misses of every difficulty level on top of the game
+pub fn stats(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    rectangle(
+        [0.0, 0.0, 0.0, 0.85],
+        [0.0, 0.0, app.window_size[0], app.window_size[1]],
+        c.transform,
+        &mut app.gl,
+    );
+    let mut lines = vec![
+        "STATISTICS".to_string(),
+        String::new(),
+        format!("{:<10} {:>4} {:>4} {:>7} {:>6}", "", "won", "lost", "streak", "misses"),
+    ];
+    for difficulty in &app.difficulties {
+        let stats = app.stats.get(difficulty).cloned().unwrap_or_default();
+        // long custom names are cut to keep the columns lined up
+        let name: String = difficulty.name().chars().take(10).collect();
+        lines.push(format!(
+            "{:<10} {:>4} {:>4} {:>3}/{:<3} {:>6.1}",
+            name,
+            stats.wins,
+            stats.losses,
+            stats.current_streak,
+            stats.best_streak,
+            stats.average_misses()
+        ));
+    }
+    lines.push(String::new());
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.45) as u32;
+    let line_spacing = scaled_font_size as f64 * 1.6;
+    let left = app.window_size[0] / 2.0 - WINDOW_DOTS * 0.4 * app.scale;
+    let top = app.window_size[1] / 2.0 - line_spacing * lines.len() as f64 / 2.0;
+    for (i, line) in lines.iter().enumerate() {
+        text(
+            WHITE,
+            scaled_font_size,
+            line,
+            &mut app.glyph_cache,
+            c.transform.trans(left, top + i as f64 * line_spacing),
+            &mut app.gl,
+        )?;
+    }
+    text(
+        GREY,
+        scaled_font_size,
+        "press any key to continue",
+        &mut app.glyph_cache,
+        c.transform
+            .trans(left, top + lines.len() as f64 * line_spacing),
+        &mut app.gl,
+    )
+}
+
+/// draws the instructions for how to select a word and difficulty on top of the game
+pub fn instructions(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    rectangle(
+        [0.0, 0.0, 0.0, 0.85],
+        [0.0, 0.0, app.window_size[0], app.window_size[1]],
+        c.transform,
+        &mut app.gl,
+    );
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.45) as u32;
+    // the lines move closer together when they would not fit in the window
+    let line_spacing = (scaled_font_size as f64 * 1.6)
+        .min(app.window_size[1] * 0.95 / (INSTRUCTIONS.len() + 1) as f64);
+    let left = app.window_size[0] / 2.0 - WINDOW_DOTS * 0.4 * app.scale;
+    let top = app.window_size[1] / 2.0 - line_spacing * INSTRUCTIONS.len() as f64 / 2.0;
+    for (i, line) in INSTRUCTIONS.iter().enumerate() {
+        text(
+            WHITE,
+            scaled_font_size,
+            line,
+            &mut app.glyph_cache,
+            c.transform.trans(left, top + i as f64 * line_spacing),
+            &mut app.gl,
+        )?;
+    }
+    text(
+        GREY,
+        scaled_font_size,
+        "press any key to continue",
+        &mut app.glyph_cache,
+        c.transform
+            .trans(left, top + INSTRUCTIONS.len() as f64 * line_spacing),
+        &mut app.gl,
+    )
+}
+
+/// draws who is playing over the network, or why the network game ended, in the top left corner
+pub fn network(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.4) as u32;
+    let (label, color) = match &app.peer {
+        Some(Peer::Waiting(listener)) => match listener.local_addr() {
+            Ok(address) => (format!("waiting for a player on {address}"), GREY),
+            Err(_) => ("waiting for a player".to_string(), GREY),
+        },
+        Some(Peer::Guesser(connection)) => (format!("{} is guessing", connection.peer_name()), GREY),
+        Some(Peer::Setter(connection)) if app.state == AppState::Selecting => {
+            (format!("{} is picking a word", connection.peer_name()), GREY)
+        }
+        Some(Peer::Setter(connection)) => (format!("{} set the word", connection.peer_name()), GREY),
+        None => match &app.network_status {
+            Some(status) => (status.clone(), LOSS_RED),
+            None => return Ok(()),
+        },
+    };
+    text(
+        color,
+        scaled_font_size,
+        &label,
+        &mut app.glyph_cache,
+        c.transform.trans(scaled_font_size as f64 * 0.65, scaled_font_size as f64 * 1.5),
+        &mut app.gl,
+    )
+}
+
+/// draws a reminder of how to open the instructions in the top right corner
+pub fn help_hint(app: &mut App, c: &Context, font_size: u32) -> Result<(), String> {
+    let scaled_font_size: u32 = (font_size as f64 * app.scale * 0.4) as u32;
+    let hint = if app.ai_guesser {
+        "F1: help  F3: computer guessing"
+    } else {
+        "F1: help"
+    };
+    text(
+        GREY,
+        scaled_font_size,
+        hint,
+        &mut app.glyph_cache,
+        c.transform.trans(
+            app.window_size[0] - scaled_font_size as f64 * 0.65 * (hint.len() + 1) as f64,
+            scaled_font_size as f64 * 1.5,
+        ),
+        &mut app.gl,
+    )
+}
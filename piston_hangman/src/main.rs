@@ -4,15 +4,21 @@ extern crate graphics;
 extern crate opengl_graphics;
 extern crate piston;
 mod app;
-mod game;
 use app::*;
+use hangman_core::hotseat::Match;
+use hangman_core::replay::Replay;
+use hangman_core::{Language, WordList};
+use glutin_window::glutin::window::CursorIcon;
 use glutin_window::GlutinWindow as Window;
 use opengl_graphics::OpenGL;
 use piston::event_loop::{EventSettings, Events};
 use piston::input::mouse::MouseCursorEvent;
 use piston::input::{RenderEvent, UpdateEvent};
 use piston::window::WindowSettings;
-use piston::{EventLoop, ButtonEvent, ResizeEvent};
+use piston::{EventLoop, ButtonEvent, ResizeEvent, TextEvent};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::path::Path;
 
 fn main() {
     let opengl = OpenGL::V3_2;
@@ -28,13 +34,108 @@ fn main() {
     
     // Create a new game and run it.
     let mut app = App::default();
+    let args: Vec<String> = std::env::args().collect();
+    // `--seed <number>` makes the random words repeatable
+    if let Some(seed) = arg_value(&args, "--seed").and_then(|s| s.parse::<u64>().ok()) {
+        app.rng = StdRng::seed_from_u64(seed);
+    }
+    // `--hints <number>` sets how many hints are allowed per game
+    if let Some(max_hints) = arg_value(&args, "--hints").and_then(|s| s.parse::<u32>().ok()) {
+        app.game.max_hints = max_hints;
+    }
+    // `--solve-penalty <number>` sets how many lives a wrong attempt at the whole word costs
+    if let Some(penalty) = arg_value(&args, "--solve-penalty").and_then(|s| s.parse::<u32>().ok()) {
+        if app.difficulties.iter().any(|d| d.allows_solve_penalty(penalty)) {
+            app.game.solve_penalty = penalty;
+        } else {
+            let most_lives = app.difficulties.iter().map(|d| d.lives()).max().unwrap_or(0);
+            eprintln!("--solve-penalty expects 1 to {most_lives} lives, keeping {}", app.game.solve_penalty);
+        }
+    }
+    // `--strict` shows accented letters on their own instead of folding them into their base letter
+    if args.iter().any(|a| a == "--strict") {
+        app.game.fold_diacritics = false;
+        app.select_language(app.language_index);
+    }
+    // `--language <code>` sets the language of the first game
+    if let Some(name) = arg_value(&args, "--language") {
+        match Language::find(name).and_then(|l| app.languages.iter().position(|a| *a == l)) {
+            Some(index) => app.select_language(index),
+            None => eprintln!("unknown language \"{name}\""),
+        }
+    }
+    // `--word-list <path>` replaces the words of the language with a .txt or .zst file
+    if let Some(path) = arg_value(&args, "--word-list") {
+        let index = app.language_index;
+        match WordList::load(Path::new(path), &app.languages[index]) {
+            Ok(word_list) => {
+                app.languages[index] = app.languages[index].clone().with_word_list(word_list);
+                app.select_language(index);
+            }
+            Err(e) => eprintln!("skipping the custom word list, {e}"),
+        }
+    }
+    // `--best-of <number>` sets the most rounds of a match
+    if let Some(best_of) = arg_value(&args, "--best-of").and_then(|s| s.parse::<u32>().ok()) {
+        app.best_of = best_of.max(1);
+    }
+    // `--players <name>,<name>` starts a match between two players taking turns
+    if let Some(names) = arg_value(&args, "--players") {
+        match names.split_once(',') {
+            Some((first, second)) => {
+                app.hot_seat = Some(Match::new([first.to_string(), second.to_string()], app.best_of));
+            }
+            None => eprintln!("--players expects two names separated by a comma"),
+        }
+    }
+    app.load_save();
+    // `--host <address>` waits for a player to join and guess the words set here,
+    // `--join <address>` guesses the words set by the host, `--name <name>` is shown to the other player
+    if let Some(address) = arg_value(&args, "--host") {
+        app.host(address, arg_value(&args, "--name").unwrap_or("Host"));
+    }
+    else if let Some(address) = arg_value(&args, "--join") {
+        app.join(address, arg_value(&args, "--name").unwrap_or("Guest"));
+    }
+    // `--replay <path>` plays a recorded game back
+    if let Some(path) = arg_value(&args, "--replay") {
+        match Replay::load(Path::new(path)) {
+            Ok(replay) => {
+                app.show_instructions = false;
+                app.start_playback(replay);
+            }
+            Err(e) => eprintln!("can not play the replay, {e}"),
+        }
+    }
 
     let mut events = Events::new(EventSettings::new().max_fps(120));
+    let mut hovering = false;
     while let Some(e) = events.next(&mut window) {
         e.render(|args| app.render(&args));
         e.update(|args| app.update(&args));
         e.resize(|args| app.resize(&args));
         e.button(|args| app.button(&args));
+        e.text(|text| app.text(text));
         MouseCursorEvent::mouse_cursor(&e, |args| app.update_mouse_cursor(args));
+        // show a pointing hand over the letters that can be clicked
+        if app.hovered_char.is_some() != hovering {
+            hovering = app.hovered_char.is_some();
+            let cursor = if hovering {
+                CursorIcon::Hand
+            } else {
+                CursorIcon::Default
+            };
+            window.ctx.window().set_cursor_icon(cursor);
+        }
     }
+    app.autosave();
+    app.leave_network();
+}
+
+/// the value following a `--name value` command line argument
+fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
 }